@@ -3,8 +3,30 @@
 use concordium_cis2::*;
 use concordium_std::*;
 
-const SUPPORTS_STANDARDS: [StandardIdentifier<'static>; 2] =
-    [CIS0_STANDARD_IDENTIFIER, CIS2_STANDARD_IDENTIFIER];
+const CIS3_STANDARD_IDENTIFIER: StandardIdentifier<'static> =
+    StandardIdentifier::new_unchecked("CIS-3");
+
+const SUPPORTS_STANDARDS: [StandardIdentifier<'static>; 3] = [
+    CIS0_STANDARD_IDENTIFIER,
+    CIS2_STANDARD_IDENTIFIER,
+    CIS3_STANDARD_IDENTIFIER,
+];
+
+/// Hash of the genesis block of the chain the contract is deployed on
+/// (testnet), prepended to permit messages so a signature can not be replayed
+/// on another chain.
+const GENESIS_HASH: [u8; 32] = [
+    0x42, 0x21, 0x33, 0x2d, 0x34, 0xe1, 0x69, 0x41, 0x68, 0xc2, 0xa0, 0xc0, 0xb3, 0xfd, 0x0f, 0x27,
+    0x38, 0x09, 0x61, 0x2c, 0xb1, 0x3d, 0x00, 0x0d, 0x5c, 0x2e, 0x00, 0xe8, 0x5f, 0x50, 0xf7, 0x96,
+];
+
+/// Entrypoints which can be invoked through `permit`.
+const SUPPORTS_PERMIT_ENTRYPOINTS: [EntrypointName<'static>; 4] = [
+    EntrypointName::new_unchecked("mint"),
+    EntrypointName::new_unchecked("burn"),
+    EntrypointName::new_unchecked("transfer"),
+    EntrypointName::new_unchecked("updateOperator"),
+];
 
 type ContractTokenId = TokenIdU32;
 type ContractTokenAmount = TokenAmountU64;
@@ -18,9 +40,19 @@ pub struct TokenMetadata {
 }
 
 impl TokenMetadata {
+    /// An empty hash means the metadata has no hash, anything else has to be
+    /// a hex encoded SHA256 hash.
+    fn ensure_valid_hash(&self) -> ContractResult<()> {
+        ensure!(
+            self.hash.is_empty() || self.get_hash_as_bytes().is_some(),
+            ContractError::Custom(CustomContractError::InvalidMetadataHash)
+        );
+        Ok(())
+    }
+
     fn get_hash_as_bytes(&self) -> Option<[u8; 32]> {
         let mut hash_bytes: [u8; 32] = Default::default();
-        let hex_res = hex::decode_to_slice(self.hash.to_owned(), &mut hash_bytes);
+        let hex_res = hex::decode_to_slice(&self.hash, &mut hash_bytes);
         match hex_res {
             Ok(_) => Some(hash_bytes),
             Err(_) => Option::None,
@@ -35,29 +67,313 @@ impl TokenMetadata {
     }
 }
 
-#[derive(Serial, Deserial, SchemaType)]
-struct TokenParams {
-    amount: TokenAmountU64,
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, SchemaType)]
+enum TransferPolicy {
+    Soulbound,
+    Free,
+    OperatorOnly,
+}
+
+/// Collection a token joins, with the cap on how many distinct tokens of the
+/// collection a single account can mint. The cap is kept once per collection:
+/// the first token giving one sets it, later tokens leave it out or repeat it.
+#[derive(Debug, Serialize, Clone, SchemaType)]
+struct CollectionCap {
+    name: String,
+    max_tokens_per_account: Option<u32>,
+}
+
+/// Sealed-bid mode of an auction. Bids are committed with a deposit during
+/// the auction window and revealed between its end and `reveal_end`.
+#[derive(Debug, Serialize, Clone, SchemaType)]
+struct SealedBidConfig {
+    reveal_end: Timestamp,
+    min_deposit: Amount,
+    /// Share of the deposit in basis points kept from bidders who never reveal.
+    forfeit_bps: u16,
+}
+
+impl SealedBidConfig {
+    fn forfeit(&self, deposit: Amount) -> Amount {
+        let forfeit = u128::from(deposit.micro_ccd) * u128::from(self.forfeit_bps) / 10_000;
+        Amount::from_micro_ccd(forfeit as u64)
+    }
+}
+
+/// Price of a paid mint, falling linearly from `start_price` at the start of
+/// the auction window to `floor_price` at its end.
+#[derive(Debug, Serialize, Clone, SchemaType)]
+struct DutchPrice {
+    start_price: Amount,
+    floor_price: Amount,
+}
+
+/// Recipient of a share of the proceeds of a token in basis points.
+#[derive(Debug, Serialize, Clone, SchemaType)]
+struct Payout {
+    recipient: AccountAddress,
+    share_bps: u16,
+}
+
+#[derive(Debug, Serialize, Clone, SchemaType)]
+struct TokenInfo {
+    metadata: TokenMetadata,
     max_supply: ContractTokenAmount,
+    /// Maximum amount of the token a single account can mint in total.
+    max_per_account: ContractTokenAmount,
+    transfer_policy: TransferPolicy,
+    start: Option<Timestamp>,
+    end: Option<Timestamp>,
+    collection: Option<CollectionCap>,
+    /// Root of the Merkle tree of accounts allowed to mint without a voucher.
+    merkle_root: Option<HashSha2256>,
+    sealed_bid: Option<SealedBidConfig>,
+    price: Option<DutchPrice>,
+    /// Split of sales and paid mints. All proceeds go to the admin when empty.
+    payouts: Vec<Payout>,
+    /// Id of the identity statement a voucher must be issued for to mint the
+    /// token. Vouchers for any statement are accepted when not set.
+    statement_id: Option<String>,
+}
+
+impl TokenInfo {
+    fn ensure_valid_window(&self) -> ContractResult<()> {
+        if let (Some(start), Some(end)) = (self.start, self.end) {
+            ensure!(
+                start < end,
+                ContractError::Custom(CustomContractError::InvalidAuctionWindow)
+            );
+        }
+        if let Some(sealed_bid) = &self.sealed_bid {
+            ensure!(
+                self.end.is_some_and(|end| end < sealed_bid.reveal_end),
+                ContractError::Custom(CustomContractError::InvalidAuctionWindow)
+            );
+        }
+        Ok(())
+    }
+
+    fn ensure_valid_price(&self) -> ContractResult<()> {
+        if let Some(price) = &self.price {
+            ensure!(
+                self.start.is_some() && self.end.is_some(),
+                ContractError::Custom(CustomContractError::InvalidAuctionWindow)
+            );
+            ensure!(
+                price.start_price >= price.floor_price,
+                ContractError::Custom(CustomContractError::InvalidPrice)
+            );
+        }
+        Ok(())
+    }
+
+    /// Price of a single unit of the token at `now`.
+    fn current_price(&self, now: Timestamp) -> Amount {
+        let (price, start, end) = match (&self.price, self.start, self.end) {
+            (Some(price), Some(start), Some(end)) => (price, start, end),
+            _ => return Amount::zero(),
+        };
+        if now <= start {
+            return price.start_price;
+        }
+        if now >= end {
+            return price.floor_price;
+        }
+
+        let drop = price.start_price.micro_ccd - price.floor_price.micro_ccd;
+        let elapsed = now.timestamp_millis() - start.timestamp_millis();
+        let duration = end.timestamp_millis() - start.timestamp_millis();
+        let dropped = u128::from(drop) * u128::from(elapsed) / u128::from(duration);
+        Amount::from_micro_ccd(price.start_price.micro_ccd - dropped as u64)
+    }
+
+    fn ensure_valid_payouts(&self) -> ContractResult<()> {
+        let total: u32 = self.payouts.iter().map(|payout| u32::from(payout.share_bps)).sum();
+        ensure!(
+            self.payouts.is_empty() || total == 10_000,
+            ContractError::Custom(CustomContractError::InvalidPayoutShares)
+        );
+        Ok(())
+    }
+
+    fn ensure_valid_sealed_bid(&self) -> ContractResult<()> {
+        ensure!(
+            self.sealed_bid
+                .as_ref()
+                .is_none_or(|sealed_bid| sealed_bid.forfeit_bps <= 10_000),
+            ContractError::Custom(CustomContractError::InvalidSealedBidConfig)
+        );
+        Ok(())
+    }
+
+    fn sealed_bid(&self) -> ContractResult<&SealedBidConfig> {
+        self.sealed_bid
+            .as_ref()
+            .ok_or(ContractError::Custom(CustomContractError::NotSealedBidAuction))
+    }
+
+    /// Fail unless `now` is within the reveal phase of a sealed-bid auction.
+    fn ensure_reveal_open(&self, now: Timestamp) -> ContractResult<()> {
+        let sealed_bid = self.sealed_bid()?;
+        ensure!(
+            self.has_ended(now),
+            ContractError::Custom(CustomContractError::AuctionNotEnded)
+        );
+        ensure!(
+            now < sealed_bid.reveal_end,
+            ContractError::Custom(CustomContractError::AuctionEnded)
+        );
+        Ok(())
+    }
+
+    fn has_ended(&self, now: Timestamp) -> bool {
+        self.end.is_some_and(|end| now >= end)
+    }
+
+    fn ensure_open(&self, now: Timestamp) -> ContractResult<()> {
+        ensure!(
+            self.start.is_none_or(|start| now >= start),
+            ContractError::Custom(CustomContractError::AuctionNotStarted)
+        );
+        ensure!(
+            !self.has_ended(now),
+            ContractError::Custom(CustomContractError::AuctionEnded)
+        );
+        Ok(())
+    }
 }
 
 #[derive(Serial, Deserial, SchemaType)]
-struct MintParams {
+struct MintVoucher {
+    contract: ContractAddress,
+    account: AccountAddress,
+    /// Id of the statement the account proved to the verifier.
+    statement_id: String,
     tokens: collections::BTreeSet<ContractTokenId>,
-    signature: SignatureEd25519,
+    nonce: u64,
+    expiry: Timestamp,
+}
+
+/// Proof that the sender is allowed to mint, either a voucher signed by the
+/// verifier or a Merkle proof that the sender is in the allowlist of each
+/// token.
+#[derive(Serial, Deserial, SchemaType)]
+enum MintAuthorization {
+    Voucher {
+        voucher: MintVoucher,
+        signature: SignatureEd25519,
+    },
+    Allowlist {
+        proof: Vec<HashSha2256>,
+    },
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+struct MintParams {
+    tokens: collections::BTreeMap<ContractTokenId, ContractTokenAmount>,
+    authorization: MintAuthorization,
+    /// Receiver of the tokens, the sender when not set.
+    to: Option<Receiver>,
+}
+
+/// Check that the account is a leaf of the Merkle tree with the given root.
+/// Leaves are the SHA256 hash of the account address and each parent is the
+/// hash of its two children in ascending order.
+fn verify_merkle_proof(
+    crypto_primitives: &impl HasCryptoPrimitives,
+    account: &AccountAddress,
+    proof: &[HashSha2256],
+    root: HashSha2256,
+) -> bool {
+    let mut node = crypto_primitives.hash_sha2_256(&account.0);
+    for sibling in proof {
+        let (left, right) = if node.0 <= sibling.0 {
+            (node.0, sibling.0)
+        } else {
+            (sibling.0, node.0)
+        };
+        let mut pair = [0u8; 64];
+        pair[..32].copy_from_slice(&left);
+        pair[32..].copy_from_slice(&right);
+        node = crypto_primitives.hash_sha2_256(&pair);
+    }
+    node == root
+}
+
+/// Check the authorization of `account` that does not depend on the token,
+/// using up the nonce of a voucher.
+fn authorize_account<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    state: &mut State<S>,
+    crypto_primitives: &impl HasCryptoPrimitives,
+    account: AccountAddress,
+    authorization: &MintAuthorization,
+) -> ContractResult<()> {
+    if let MintAuthorization::Voucher { voucher, signature } = authorization {
+        ensure!(
+            voucher.contract == ctx.self_address() && voucher.account == account,
+            ContractError::Unauthorized
+        );
+        ensure!(
+            voucher.expiry > ctx.metadata().slot_time(),
+            ContractError::Custom(CustomContractError::VoucherExpired)
+        );
+
+        let verify = state.verify_with_any_key(crypto_primitives, *signature, &to_bytes(voucher));
+
+        ensure!(verify, ContractError::Unauthorized);
+
+        ensure!(
+            state.used_nonces.insert(voucher.nonce),
+            ContractError::Custom(CustomContractError::VoucherAlreadyUsed)
+        );
+    }
+    Ok(())
+}
+
+/// Check that the authorization of `account` covers the token.
+fn ensure_token_authorized(
+    crypto_primitives: &impl HasCryptoPrimitives,
+    account: &AccountAddress,
+    authorization: &MintAuthorization,
+    token_id: &ContractTokenId,
+    token: &TokenInfo,
+) -> ContractResult<()> {
+    match authorization {
+        MintAuthorization::Voucher { voucher, .. } => {
+            ensure!(
+                voucher.tokens.contains(token_id),
+                ContractError::Custom(CustomContractError::TokenNotInVoucher)
+            );
+            ensure!(
+                token
+                    .statement_id
+                    .as_ref()
+                    .is_none_or(|statement_id| *statement_id == voucher.statement_id),
+                ContractError::Custom(CustomContractError::StatementMismatch)
+            );
+        }
+        MintAuthorization::Allowlist { proof } => {
+            let allowed = token.merkle_root.is_some_and(|root| {
+                verify_merkle_proof(crypto_primitives, account, proof, root)
+            });
+            ensure!(allowed, ContractError::Custom(CustomContractError::NotInAllowlist));
+        }
+    }
+    Ok(())
 }
 
-#[derive(Serial, DeserialWithState, Deletable, StateClone)]
+#[derive(Serial, DeserialWithState, Deletable)]
 #[concordium(state_parameter = "S")]
 struct AddressState<S> {
-    balances: StateSet<ContractTokenId, S>,
+    balances: StateMap<ContractTokenId, ContractTokenAmount, S>,
     operators: StateSet<Address, S>,
 }
 
 impl<S: HasStateApi> AddressState<S> {
     fn empty(state_builder: &mut StateBuilder<S>) -> Self {
         AddressState {
-            balances: state_builder.new_set(),
+            balances: state_builder.new_map(),
             operators: state_builder.new_set(),
         }
     }
@@ -65,17 +381,210 @@ impl<S: HasStateApi> AddressState<S> {
 
 #[derive(Serial, Deserial, SchemaType)]
 struct BurnParams {
-    token_id: ContractTokenId
+    token_id: ContractTokenId,
+    amount: ContractTokenAmount,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq, SchemaType)]
+struct AuctionState {
+    highest_bid: Amount,
+    highest_bidder: Option<AccountAddress>,
+    closed: bool,
+}
+
+impl Default for AuctionState {
+    fn default() -> Self {
+        AuctionState {
+            highest_bid: Amount::zero(),
+            highest_bidder: None,
+            closed: false,
+        }
+    }
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+struct AuctionParams {
+    token_id: ContractTokenId,
+}
+
+/// Bid committed to a sealed-bid auction that is not yet revealed.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+struct SealedBid {
+    /// SHA256 hash of the bid amount in micro CCD as a little-endian `u64`
+    /// followed by a 32 byte salt.
+    commitment: HashSha2256,
+    deposit: Amount,
+}
+
+/// Lottery deciding which entrants can mint a token. The admin commits to
+/// the hash of a seed before entries open and reveals the seed once the
+/// auction window has ended, which shuffles the first `winners` entrants into
+/// place.
+#[derive(Serial, DeserialWithState, Deletable)]
+#[concordium(state_parameter = "S")]
+struct Lottery<S> {
+    seed_hash: HashSha2256,
+    seed: Option<HashSha2256>,
+    entrant_count: u32,
+    winners: u32,
+    entrants: StateMap<u32, AccountAddress, S>,
+    /// Index of each entrant in `entrants`. Removed once a winner has minted.
+    entries: StateMap<AccountAddress, u32, S>,
+}
+
+impl<S: HasStateApi> Lottery<S> {
+    fn empty(seed_hash: HashSha2256, state_builder: &mut StateBuilder<S>) -> Self {
+        Lottery {
+            seed_hash,
+            seed: None,
+            entrant_count: 0,
+            winners: 0,
+            entrants: state_builder.new_map(),
+            entries: state_builder.new_map(),
+        }
+    }
+
+    fn enter(&mut self, account: AccountAddress) -> ContractResult<u32> {
+        let index = self.entrant_count;
+        ensure!(
+            self.entries.insert(account, index).is_none(),
+            ContractError::Custom(CustomContractError::LotteryAlreadyEntered)
+        );
+        self.entrants.insert(index, account);
+        self.entrant_count += 1;
+        Ok(index)
+    }
+
+    /// Select the winners with a partial Fisher-Yates shuffle of the
+    /// entrants, drawing position `i` from the first eight bytes of
+    /// `sha256(seed || i)` with `i` as a little-endian `u32`.
+    fn reveal(
+        &mut self,
+        crypto_primitives: &impl HasCryptoPrimitives,
+        seed: HashSha2256,
+        max_winners: u32,
+    ) -> ContractResult<u32> {
+        ensure!(
+            self.seed.is_none(),
+            ContractError::Custom(CustomContractError::LotteryAlreadyRevealed)
+        );
+        ensure!(
+            crypto_primitives.hash_sha2_256(&seed.0) == self.seed_hash,
+            ContractError::Custom(CustomContractError::InvalidLotterySeed)
+        );
+
+        let entrants = self.entrant_count;
+        let winners = max_winners.min(entrants);
+        for i in 0..winners {
+            let mut message = [0u8; 36];
+            message[..32].copy_from_slice(&seed.0);
+            message[32..].copy_from_slice(&i.to_le_bytes());
+            let hash = crypto_primitives.hash_sha2_256(&message);
+            let mut random = [0u8; 8];
+            random.copy_from_slice(&hash.0[..8]);
+            let j = i + (u64::from_le_bytes(random) % u64::from(entrants - i)) as u32;
+
+            if i != j {
+                let first = *self.entrants.get(&i).unwrap_abort();
+                let second = *self.entrants.get(&j).unwrap_abort();
+                self.entrants.insert(i, second);
+                self.entrants.insert(j, first);
+                self.entries.insert(second, i);
+                self.entries.insert(first, j);
+            }
+        }
+
+        self.seed = Some(seed);
+        self.winners = winners;
+        Ok(winners)
+    }
+
+    /// Use the right of a winner to mint a single unit of the token.
+    fn claim(&mut self, account: &AccountAddress, amount: ContractTokenAmount) -> ContractResult<()> {
+        ensure!(
+            self.seed.is_some(),
+            ContractError::Custom(CustomContractError::LotteryNotRevealed)
+        );
+        ensure!(
+            self.entries.get(account).is_some_and(|index| *index < self.winners),
+            ContractError::Custom(CustomContractError::NotLotteryWinner)
+        );
+        ensure!(
+            amount == 1.into(),
+            ContractError::Custom(CustomContractError::InvalidLotteryClaim)
+        );
+        self.entries.remove(account);
+        Ok(())
+    }
+}
+
+/// Named group of tokens. Tokens join the collection named by their
+/// `CollectionCap` when they are created and leave it when they are burned.
+#[derive(Serial, DeserialWithState, Deletable)]
+#[concordium(state_parameter = "S")]
+struct Collection<S> {
+    metadata: Option<TokenMetadata>,
+    /// Cap on how many distinct tokens of the collection a single account can
+    /// mint. Accounts can mint any number of them when not set.
+    max_tokens_per_account: Option<u32>,
+    tokens: StateSet<ContractTokenId, S>,
+}
+
+impl<S: HasStateApi> Collection<S> {
+    fn empty(state_builder: &mut StateBuilder<S>) -> Self {
+        Collection {
+            metadata: None,
+            max_tokens_per_account: None,
+            tokens: state_builder.new_set(),
+        }
+    }
 }
 
-#[derive(Serial, DeserialWithState, StateClone)]
+/// Accounts with a mint recorded for a token, so their mint counters can be
+/// cleared when the token is burned.
+#[derive(Serial, DeserialWithState, Deletable)]
+#[concordium(state_parameter = "S")]
+struct Minters<S> {
+    /// Name of the collection the mints of the token count towards.
+    collection: Option<String>,
+    accounts: StateSet<AccountAddress, S>,
+}
+
+/// Version of the layout of `State`, stored as its first field so a
+/// `migrate` run after an upgrade can read it before the rest of the state.
+const STATE_VERSION: u32 = 7;
+
+#[derive(Serial, DeserialWithState)]
 #[concordium(state_parameter = "S")]
 struct State<S> {
+    state_version: u32,
     state: StateMap<Address, AddressState<S>, S>,
-    tokens: StateMap<ContractTokenId, (TokenMetadata, ContractTokenAmount), S>,
+    tokens: StateMap<ContractTokenId, TokenInfo, S>,
     token_balance: StateMap<ContractTokenId, ContractTokenAmount, S>,
+    holders: StateMap<ContractTokenId, StateSet<Address, S>, S>,
+    minted: StateMap<(AccountAddress, ContractTokenId), ContractTokenAmount, S>,
+    collection_mints: StateMap<(AccountAddress, String), u32, S>,
+    nonces: StateMap<AccountAddress, u64, S>,
+    locked_metadata: StateSet<ContractTokenId, S>,
     implementors: StateMap<StandardIdentifierOwned, Vec<ContractAddress>, S>,
-    verify_key: PublicKeyEd25519,
+    verify_keys: StateSet<PublicKeyEd25519, S>,
+    admin: AccountAddress,
+    proposed_admin: Option<AccountAddress>,
+    roles: StateSet<(Address, Role), S>,
+    paused: bool,
+    auctions: StateMap<ContractTokenId, AuctionState, S>,
+    lotteries: StateMap<ContractTokenId, Lottery<S>, S>,
+    sealed_bids: StateMap<ContractTokenId, StateMap<AccountAddress, SealedBid, S>, S>,
+    pending_returns: StateMap<AccountAddress, Amount, S>,
+    used_nonces: StateSet<u64, S>,
+    /// Proceeds credited to each payout recipient and not yet withdrawn.
+    proceeds: StateMap<AccountAddress, Amount, S>,
+    /// Id handed out by the next `createAuctions`, unless already in use.
+    next_token_id: u32,
+    collections: StateMap<String, Collection<S>, S>,
+    /// Tokens that can no longer be minted. Existing holders keep them.
+    minting_closed: StateSet<ContractTokenId, S>,
+    minters: StateMap<ContractTokenId, Minters<S>, S>,
 }
 
 #[derive(Debug, Serialize, SchemaType)]
@@ -91,13 +600,68 @@ enum CustomContractError {
     LogFull,
     LogMalformed,
     InvalidContractName,
-    ContractOnly,
+    AccountOnly,
     InvokeContractError,
     TokenAlreadyCreated,
     TokenNotCreated,
     AuctionNotInitialized,
     MaxSupplyReached,
     NoBalanceToBurn,
+    InvokeTransferError,
+    AuctionClosed,
+    BidTooLow,
+    NothingToClaim,
+    VoucherExpired,
+    VoucherAlreadyUsed,
+    TokenNotInVoucher,
+    VerifyKeyAlreadyActive,
+    VerifyKeyNotActive,
+    InvalidRole,
+    Paused,
+    AuctionNotStarted,
+    AuctionEnded,
+    InvalidAuctionWindow,
+    HoldersNotPurged,
+    AccountCapReached,
+    CollectionCapReached,
+    PermitExpired,
+    WrongNonce,
+    PublicKeyNotRegistered,
+    WrongEntrypoint,
+    InvalidMetadataHash,
+    NotInAllowlist,
+    MetadataLocked,
+    LotteryAlreadyCommitted,
+    LotteryNotCommitted,
+    LotteryAlreadyEntered,
+    LotteryAlreadyRevealed,
+    LotteryNotRevealed,
+    InvalidLotterySeed,
+    NotLotteryWinner,
+    AuctionNotEnded,
+    InvalidSealedBidConfig,
+    NotSealedBidAuction,
+    SealedBidAuction,
+    DepositTooLow,
+    BidAlreadyCommitted,
+    BidNotCommitted,
+    InvalidBidReveal,
+    SealedBidsNotSettled,
+    InvalidPrice,
+    InsufficientPayment,
+    NothingToWithdraw,
+    InvalidPayoutShares,
+    FailedUpgradeMissingModule,
+    FailedUpgradeMissingContract,
+    FailedUpgradeUnsupportedModuleVersion,
+    UnknownStateVersion,
+    StatementMismatch,
+    CollectionNotFound,
+    MaxSupplyBelowCirculating,
+    MintingClosed,
+    PriceOverflow,
+    CollectionCapMismatch,
+    InvalidLotteryClaim,
 }
 
 type ContractError = Cis2Error<CustomContractError>;
@@ -119,6 +683,22 @@ impl<T> From<CallContractError<T>> for CustomContractError {
     }
 }
 
+impl From<TransferError> for CustomContractError {
+    fn from(_te: TransferError) -> Self {
+        Self::InvokeTransferError
+    }
+}
+
+impl From<UpgradeError> for CustomContractError {
+    fn from(ue: UpgradeError) -> Self {
+        match ue {
+            UpgradeError::MissingModule => Self::FailedUpgradeMissingModule,
+            UpgradeError::MissingContract => Self::FailedUpgradeMissingContract,
+            UpgradeError::UnsupportedModuleVersion => Self::FailedUpgradeUnsupportedModuleVersion,
+        }
+    }
+}
+
 impl From<CustomContractError> for ContractError {
     fn from(c: CustomContractError) -> Self {
         Cis2Error::Custom(c)
@@ -137,943 +717,8363 @@ impl From<NewContractNameError> for CustomContractError {
     }
 }
 
-impl<S: HasStateApi> State<S> {
-    fn empty(state_builder: &mut StateBuilder<S>, verify_key: PublicKeyEd25519) -> Self {
-        State {
-            state: state_builder.new_map(),
-            tokens: state_builder.new_map(),
-            token_balance: state_builder.new_map(),
-            implementors: state_builder.new_map(),
-            verify_key,
-        }
-    }
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Role {
+    Admin,
+    AuctionManager,
+    MetadataManager,
+}
 
-    fn mint(
-        &mut self,
-        token_id: &ContractTokenId,
-        owner: &Address,
-        state_builder: &mut StateBuilder<S>,
-    ) {
-        let mut owner_state = self
-            .state
-            .entry(*owner)
-            .or_insert_with(|| AddressState::empty(state_builder));
+impl schema::SchemaType for Role {
+    fn get_type() -> schema::Type {
+        schema::Type::Enum(vec![
+            ("Admin".to_string(), schema::Fields::None),
+            ("AuctionManager".to_string(), schema::Fields::None),
+            ("MetadataManager".to_string(), schema::Fields::None),
+        ])
+    }
+}
 
-        owner_state.balances.insert(*token_id);
+const VERIFY_KEY_CHANGED_EVENT_TAG: u8 = 0;
+const ADMIN_PROPOSED_EVENT_TAG: u8 = 1;
+const ADMIN_CHANGED_EVENT_TAG: u8 = 2;
+const ROLE_CHANGED_EVENT_TAG: u8 = 3;
+const PAUSED_CHANGED_EVENT_TAG: u8 = 4;
+const AUCTION_WINDOW_UPDATED_EVENT_TAG: u8 = 5;
+const AUCTION_CREATED_EVENT_TAG: u8 = 6;
+const AUCTION_REMOVED_EVENT_TAG: u8 = 7;
+const IMPLEMENTORS_SET_EVENT_TAG: u8 = 8;
+const METADATA_LOCKED_EVENT_TAG: u8 = 9;
+const LOTTERY_COMMITTED_EVENT_TAG: u8 = 10;
+const LOTTERY_ENTERED_EVENT_TAG: u8 = 11;
+const LOTTERY_REVEALED_EVENT_TAG: u8 = 12;
+const SEALED_BID_COMMITTED_EVENT_TAG: u8 = 13;
+const SEALED_BID_REVEALED_EVENT_TAG: u8 = 14;
+const PROCEEDS_CREDITED_EVENT_TAG: u8 = 15;
+const PROCEEDS_WITHDRAWN_EVENT_TAG: u8 = 16;
+const COLLECTION_METADATA_SET_EVENT_TAG: u8 = 17;
+const MAX_SUPPLY_UPDATED_EVENT_TAG: u8 = 18;
+const MINTING_CLOSED_EVENT_TAG: u8 = 19;
+/// Tag of the CIS-3 nonce event.
+const NONCE_EVENT_TAG: u8 = u8::MAX - 5;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+enum VerifyKeyUpdate {
+    Added,
+    Revoked,
+}
 
-        let mut circulating = self
-            .token_balance
-            .entry(*token_id)
-            .or_insert_with(|| 0.into());
-        *circulating += 1.into();
+impl schema::SchemaType for VerifyKeyUpdate {
+    fn get_type() -> schema::Type {
+        schema::Type::Enum(vec![
+            ("Added".to_string(), schema::Fields::None),
+            ("Revoked".to_string(), schema::Fields::None),
+        ])
     }
+}
 
-    fn burn(&mut self, token_id: &ContractTokenId, owner: &Address) -> ContractResult<()> {
-        let owner_state_option = self.state.get_mut(owner);
+#[derive(Debug, Serialize)]
+struct VerifyKeyChangedEvent {
+    key: PublicKeyEd25519,
+    update: VerifyKeyUpdate,
+}
 
-        ensure!(
-            owner_state_option.is_some(),
-            ContractError::Custom(CustomContractError::NoBalanceToBurn)
-        );
+#[derive(Debug, Serialize)]
+struct AdminProposedEvent {
+    proposed: AccountAddress,
+}
 
-        let mut address_state = owner_state_option.unwrap();
+#[derive(Debug, Serialize)]
+struct AdminChangedEvent {
+    previous: AccountAddress,
+    admin: AccountAddress,
+}
 
-        let removed = address_state.balances.remove(&token_id);
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+enum RoleUpdate {
+    Granted,
+    Revoked,
+}
 
-        ensure!(removed, ContractError::Custom(CustomContractError::NoBalanceToBurn));
+impl schema::SchemaType for RoleUpdate {
+    fn get_type() -> schema::Type {
+        schema::Type::Enum(vec![
+            ("Granted".to_string(), schema::Fields::None),
+            ("Revoked".to_string(), schema::Fields::None),
+        ])
+    }
+}
 
-        let mut circulating = self
-            .token_balance
-            .entry(*token_id)
-            .or_insert_with(|| 0.into());
-        *circulating -= 1.into();
+#[derive(Debug, Serialize)]
+struct RoleChangedEvent {
+    address: Address,
+    role: Role,
+    update: RoleUpdate,
+}
 
-        Ok(())
-    }
+#[derive(Debug, Serialize)]
+struct PausedChangedEvent {
+    paused: bool,
+}
 
-    #[inline(always)]
-    fn contains_token(&self, token_id: &ContractTokenId) -> bool {
-        self.tokens.get(&token_id).is_some()
-    }
+#[derive(Debug, Serialize)]
+struct AuctionWindowUpdatedEvent {
+    token_id: ContractTokenId,
+    start: Option<Timestamp>,
+    end: Option<Timestamp>,
+}
 
-    fn balance(
-        &self,
-        token_id: &ContractTokenId,
-        address: &Address,
-    ) -> ContractResult<ContractTokenAmount> {
-        ensure!(self.contains_token(token_id), ContractError::InvalidTokenId);
-        let balance = self.state.get(address).map_or(0, |address_state| {
-        let contains = address_state.balances.contains(&token_id);
+#[derive(Debug, Serialize)]
+struct AuctionCreatedEvent {
+    token_id: ContractTokenId,
+    max_supply: ContractTokenAmount,
+}
 
-        if contains {
-            1
-        } else {
-            0
-        }
-        });
-        Ok(balance.into())
-    }
+#[derive(Debug, Serialize)]
+struct AuctionRemovedEvent {
+    token_id: ContractTokenId,
+}
 
-    fn is_operator(&self, address: &Address, owner: &Address) -> bool {
-        self.state
-            .get(owner)
-            .map(|address_state| address_state.operators.contains(address))
-            .unwrap_or(false)
-    }
+#[derive(Debug, Serialize)]
+struct ImplementorsSetEvent {
+    id: StandardIdentifierOwned,
+    implementors: Vec<ContractAddress>,
+}
 
-    fn add_operator(
-        &mut self,
-        owner: &Address,
-        operator: &Address,
-        state_builder: &mut StateBuilder<S>,
-    ) {
-        let mut owner_state = self
-            .state
-            .entry(*owner)
-            .or_insert_with(|| AddressState::empty(state_builder));
-        owner_state.operators.insert(*operator);
-    }
+#[derive(Debug, Serialize)]
+struct MetadataLockedEvent {
+    token_id: ContractTokenId,
+}
 
-    fn remove_operator(&mut self, owner: &Address, operator: &Address) {
-        self.state.entry(*owner).and_modify(|address_state| {
-            address_state.operators.remove(operator);
-        });
-    }
+#[derive(Debug, Serialize)]
+struct LotteryCommittedEvent {
+    token_id: ContractTokenId,
+    seed_hash: HashSha2256,
+}
 
-    fn have_implementors(&self, std_id: &StandardIdentifierOwned) -> SupportResult {
-        if let Some(addresses) = self.implementors.get(std_id) {
-            SupportResult::SupportBy(addresses.to_vec())
-        } else {
-            SupportResult::NoSupport
-        }
-    }
+#[derive(Debug, Serialize)]
+struct LotteryEnteredEvent {
+    token_id: ContractTokenId,
+    account: AccountAddress,
+    index: u32,
+}
 
-    fn set_implementors(
-        &mut self,
-        std_id: StandardIdentifierOwned,
-        implementors: Vec<ContractAddress>,
-    ) {
-        self.implementors.insert(std_id, implementors);
-    }
+#[derive(Debug, Serialize)]
+struct LotteryRevealedEvent {
+    token_id: ContractTokenId,
+    seed: HashSha2256,
+    winners: u32,
+}
 
-    fn get_token_supply(&self, token_id: &ContractTokenId) -> ContractResult<ContractTokenAmount> {
-        ensure!(self.contains_token(token_id), ContractError::InvalidTokenId);
-        let supply = self
-            .tokens
-            .get(token_id)
-            .map(|info| info.1)
-            .map_or(0.into(), |v| v);
-        Ok(supply)
-    }
+#[derive(Debug, Serialize)]
+struct SealedBidCommittedEvent {
+    token_id: ContractTokenId,
+    bidder: AccountAddress,
+    deposit: Amount,
+}
 
-    fn get_circulating_supply(
-        &self,
-        token_id: &ContractTokenId,
-    ) -> ContractResult<ContractTokenAmount> {
-        ensure!(self.contains_token(token_id), ContractError::InvalidTokenId);
-        let circulating = self.token_balance.get(token_id).map_or(0.into(), |v| *v);
-        Ok(circulating)
-    }
+#[derive(Debug, Serialize)]
+struct SealedBidRevealedEvent {
+    token_id: ContractTokenId,
+    bidder: AccountAddress,
+    amount: Amount,
 }
 
-#[derive(Serial, Deserial, SchemaType)]
-struct InitParams {
-    verify_key: PublicKeyEd25519,
+#[derive(Debug, Serialize)]
+struct ProceedsCreditedEvent {
+    token_id: ContractTokenId,
+    recipient: AccountAddress,
+    amount: Amount,
 }
 
-#[init(
-    contract = "dino_auction",
-    parameter = "InitParams",
-    event = "Cis2Event<ContractTokenId, ContractTokenAmount>"
-)]
-fn contract_init<S: HasStateApi>(
-    ctx: &impl HasInitContext,
-    state_builder: &mut StateBuilder<S>,
-) -> InitResult<State<S>> {
-    let params: InitParams = ctx.parameter_cursor().get()?;
+#[derive(Debug, Serialize)]
+struct ProceedsWithdrawnEvent {
+    recipient: AccountAddress,
+    amount: Amount,
+}
 
-    Ok(State::empty(state_builder, params.verify_key))
+#[derive(Debug, Serialize)]
+struct CollectionMetadataSetEvent {
+    name: String,
+    metadata: TokenMetadata,
 }
 
-#[derive(Serialize, SchemaType)]
-struct ViewAddressState {
-    balances: Vec<ContractTokenId>,
-    operators: Vec<Address>,
+#[derive(Debug, Serialize)]
+struct MaxSupplyUpdatedEvent {
+    token_id: ContractTokenId,
+    max_supply: ContractTokenAmount,
 }
 
-#[derive(Serialize, SchemaType)]
-struct ViewState {
-    state: Vec<(Address, ViewAddressState)>,
-    tokens: Vec<ContractTokenId>,
+#[derive(Debug, Serialize)]
+struct MintingClosedEvent {
+    token_id: ContractTokenId,
 }
 
-#[receive(
-    contract = "dino_auction",
-    name = "view",
-    return_value = "ViewState"
-)]
-fn contract_view<S: HasStateApi>(
-    _ctx: &impl HasReceiveContext,
-    host: &impl HasHost<State<S>, StateApiType = S>,
-) -> ReceiveResult<ViewState> {
-    let state = host.state();
+#[derive(Debug, Serialize)]
+struct NonceEvent {
+    account: AccountAddress,
+    nonce: u64,
+}
 
-    let mut inner_state = Vec::new();
-    for (k, a_state) in state.state.iter() {
-        let mut balances = Vec::new();
-        let mut operators = Vec::new();
-        for token_id in a_state.balances.iter() {
-            balances.push(*token_id);
-        }
-        for o in a_state.operators.iter() {
-            operators.push(*o);
-        }
+type ContractCis2Event = Cis2Event<ContractTokenId, ContractTokenAmount>;
+
+/// Events logged by the contract. CIS-2 events are serialized as defined by
+/// the standard, and the tags of the custom events are kept clear of the ones
+/// reserved by the CIS standards, apart from the CIS-3 nonce event, so all
+/// events can be decoded with the event schema of the contract.
+#[derive(Debug)]
+enum ContractEvent {
+    Cis2(ContractCis2Event),
+    VerifyKeyChanged(VerifyKeyChangedEvent),
+    AdminProposed(AdminProposedEvent),
+    AdminChanged(AdminChangedEvent),
+    RoleChanged(RoleChangedEvent),
+    PausedChanged(PausedChangedEvent),
+    AuctionWindowUpdated(AuctionWindowUpdatedEvent),
+    AuctionCreated(AuctionCreatedEvent),
+    AuctionRemoved(AuctionRemovedEvent),
+    ImplementorsSet(ImplementorsSetEvent),
+    MetadataLocked(MetadataLockedEvent),
+    LotteryCommitted(LotteryCommittedEvent),
+    LotteryEntered(LotteryEnteredEvent),
+    LotteryRevealed(LotteryRevealedEvent),
+    SealedBidCommitted(SealedBidCommittedEvent),
+    SealedBidRevealed(SealedBidRevealedEvent),
+    ProceedsCredited(ProceedsCreditedEvent),
+    ProceedsWithdrawn(ProceedsWithdrawnEvent),
+    CollectionMetadataSet(CollectionMetadataSetEvent),
+    MaxSupplyUpdated(MaxSupplyUpdatedEvent),
+    MintingClosed(MintingClosedEvent),
+    Nonce(NonceEvent),
+}
 
-        inner_state.push((
-            *k,
-            ViewAddressState {
-                balances,
-                operators,
-            },
-        ));
-    }
-    let mut tokens = Vec::new();
-    for v in state.tokens.iter() {
-        tokens.push(*v.0);
+impl Serial for ContractEvent {
+    fn serial<W: Write>(&self, out: &mut W) -> Result<(), W::Err> {
+        match self {
+            ContractEvent::Cis2(event) => event.serial(out),
+            ContractEvent::VerifyKeyChanged(event) => {
+                out.write_u8(VERIFY_KEY_CHANGED_EVENT_TAG)?;
+                event.serial(out)
+            }
+            ContractEvent::AdminProposed(event) => {
+                out.write_u8(ADMIN_PROPOSED_EVENT_TAG)?;
+                event.serial(out)
+            }
+            ContractEvent::AdminChanged(event) => {
+                out.write_u8(ADMIN_CHANGED_EVENT_TAG)?;
+                event.serial(out)
+            }
+            ContractEvent::RoleChanged(event) => {
+                out.write_u8(ROLE_CHANGED_EVENT_TAG)?;
+                event.serial(out)
+            }
+            ContractEvent::PausedChanged(event) => {
+                out.write_u8(PAUSED_CHANGED_EVENT_TAG)?;
+                event.serial(out)
+            }
+            ContractEvent::AuctionWindowUpdated(event) => {
+                out.write_u8(AUCTION_WINDOW_UPDATED_EVENT_TAG)?;
+                event.serial(out)
+            }
+            ContractEvent::AuctionCreated(event) => {
+                out.write_u8(AUCTION_CREATED_EVENT_TAG)?;
+                event.serial(out)
+            }
+            ContractEvent::AuctionRemoved(event) => {
+                out.write_u8(AUCTION_REMOVED_EVENT_TAG)?;
+                event.serial(out)
+            }
+            ContractEvent::ImplementorsSet(event) => {
+                out.write_u8(IMPLEMENTORS_SET_EVENT_TAG)?;
+                event.serial(out)
+            }
+            ContractEvent::MetadataLocked(event) => {
+                out.write_u8(METADATA_LOCKED_EVENT_TAG)?;
+                event.serial(out)
+            }
+            ContractEvent::LotteryCommitted(event) => {
+                out.write_u8(LOTTERY_COMMITTED_EVENT_TAG)?;
+                event.serial(out)
+            }
+            ContractEvent::LotteryEntered(event) => {
+                out.write_u8(LOTTERY_ENTERED_EVENT_TAG)?;
+                event.serial(out)
+            }
+            ContractEvent::LotteryRevealed(event) => {
+                out.write_u8(LOTTERY_REVEALED_EVENT_TAG)?;
+                event.serial(out)
+            }
+            ContractEvent::SealedBidCommitted(event) => {
+                out.write_u8(SEALED_BID_COMMITTED_EVENT_TAG)?;
+                event.serial(out)
+            }
+            ContractEvent::SealedBidRevealed(event) => {
+                out.write_u8(SEALED_BID_REVEALED_EVENT_TAG)?;
+                event.serial(out)
+            }
+            ContractEvent::ProceedsCredited(event) => {
+                out.write_u8(PROCEEDS_CREDITED_EVENT_TAG)?;
+                event.serial(out)
+            }
+            ContractEvent::ProceedsWithdrawn(event) => {
+                out.write_u8(PROCEEDS_WITHDRAWN_EVENT_TAG)?;
+                event.serial(out)
+            }
+            ContractEvent::CollectionMetadataSet(event) => {
+                out.write_u8(COLLECTION_METADATA_SET_EVENT_TAG)?;
+                event.serial(out)
+            }
+            ContractEvent::MaxSupplyUpdated(event) => {
+                out.write_u8(MAX_SUPPLY_UPDATED_EVENT_TAG)?;
+                event.serial(out)
+            }
+            ContractEvent::MintingClosed(event) => {
+                out.write_u8(MINTING_CLOSED_EVENT_TAG)?;
+                event.serial(out)
+            }
+            ContractEvent::Nonce(event) => {
+                out.write_u8(NONCE_EVENT_TAG)?;
+                event.serial(out)
+            }
+        }
     }
-
-    Ok(ViewState {
-        state: inner_state,
-        tokens,
-    })
 }
 
-#[receive(
-    contract = "dino_auction",
-    name = "mint",
-    crypto_primitives,
-    parameter = "MintParams",
-    error = "ContractError",
-    enable_logger,
-    mutable
-)]
-fn contract_mint<S: HasStateApi>(
-    ctx: &impl HasReceiveContext,
-    host: &mut impl HasHost<State<S>, StateApiType = S>,
-    logger: &mut impl HasLogger,
-    crypto_primitives: &impl HasCryptoPrimitives,
-) -> ContractResult<()> {
-    let sender = ctx.sender();
-
-    let sender_account = match sender {
-        Address::Account(a) => a,
-        Address::Contract(_) => bail!(ContractError::Custom(CustomContractError::ContractOnly)),
-    };
+impl schema::SchemaType for ContractEvent {
+    fn get_type() -> schema::Type {
+        let mut event_map = match Cis2Event::<ContractTokenId, ContractTokenAmount>::get_type() {
+            schema::Type::TaggedEnum(event_map) => event_map,
+            _ => collections::BTreeMap::new(),
+        };
+        event_map.insert(
+            VERIFY_KEY_CHANGED_EVENT_TAG,
+            (
+                "VerifyKeyChanged".to_string(),
+                schema::Fields::Named(vec![
+                    ("key".to_string(), PublicKeyEd25519::get_type()),
+                    ("update".to_string(), VerifyKeyUpdate::get_type()),
+                ]),
+            ),
+        );
+        event_map.insert(
+            ADMIN_PROPOSED_EVENT_TAG,
+            (
+                "AdminProposed".to_string(),
+                schema::Fields::Named(vec![("proposed".to_string(), AccountAddress::get_type())]),
+            ),
+        );
+        event_map.insert(
+            ADMIN_CHANGED_EVENT_TAG,
+            (
+                "AdminChanged".to_string(),
+                schema::Fields::Named(vec![
+                    ("previous".to_string(), AccountAddress::get_type()),
+                    ("admin".to_string(), AccountAddress::get_type()),
+                ]),
+            ),
+        );
+        event_map.insert(
+            ROLE_CHANGED_EVENT_TAG,
+            (
+                "RoleChanged".to_string(),
+                schema::Fields::Named(vec![
+                    ("address".to_string(), Address::get_type()),
+                    ("role".to_string(), Role::get_type()),
+                    ("update".to_string(), RoleUpdate::get_type()),
+                ]),
+            ),
+        );
+        event_map.insert(
+            PAUSED_CHANGED_EVENT_TAG,
+            (
+                "PausedChanged".to_string(),
+                schema::Fields::Named(vec![("paused".to_string(), bool::get_type())]),
+            ),
+        );
+        event_map.insert(
+            AUCTION_WINDOW_UPDATED_EVENT_TAG,
+            (
+                "AuctionWindowUpdated".to_string(),
+                schema::Fields::Named(vec![
+                    ("token_id".to_string(), ContractTokenId::get_type()),
+                    ("start".to_string(), Option::<Timestamp>::get_type()),
+                    ("end".to_string(), Option::<Timestamp>::get_type()),
+                ]),
+            ),
+        );
+        event_map.insert(
+            AUCTION_CREATED_EVENT_TAG,
+            (
+                "AuctionCreated".to_string(),
+                schema::Fields::Named(vec![
+                    ("token_id".to_string(), ContractTokenId::get_type()),
+                    ("max_supply".to_string(), ContractTokenAmount::get_type()),
+                ]),
+            ),
+        );
+        event_map.insert(
+            AUCTION_REMOVED_EVENT_TAG,
+            (
+                "AuctionRemoved".to_string(),
+                schema::Fields::Named(vec![("token_id".to_string(), ContractTokenId::get_type())]),
+            ),
+        );
+        event_map.insert(
+            IMPLEMENTORS_SET_EVENT_TAG,
+            (
+                "ImplementorsSet".to_string(),
+                schema::Fields::Named(vec![
+                    ("id".to_string(), StandardIdentifierOwned::get_type()),
+                    ("implementors".to_string(), Vec::<ContractAddress>::get_type()),
+                ]),
+            ),
+        );
+        event_map.insert(
+            METADATA_LOCKED_EVENT_TAG,
+            (
+                "MetadataLocked".to_string(),
+                schema::Fields::Named(vec![("token_id".to_string(), ContractTokenId::get_type())]),
+            ),
+        );
+        event_map.insert(
+            LOTTERY_COMMITTED_EVENT_TAG,
+            (
+                "LotteryCommitted".to_string(),
+                schema::Fields::Named(vec![
+                    ("token_id".to_string(), ContractTokenId::get_type()),
+                    ("seed_hash".to_string(), HashSha2256::get_type()),
+                ]),
+            ),
+        );
+        event_map.insert(
+            LOTTERY_ENTERED_EVENT_TAG,
+            (
+                "LotteryEntered".to_string(),
+                schema::Fields::Named(vec![
+                    ("token_id".to_string(), ContractTokenId::get_type()),
+                    ("account".to_string(), AccountAddress::get_type()),
+                    ("index".to_string(), u32::get_type()),
+                ]),
+            ),
+        );
+        event_map.insert(
+            LOTTERY_REVEALED_EVENT_TAG,
+            (
+                "LotteryRevealed".to_string(),
+                schema::Fields::Named(vec![
+                    ("token_id".to_string(), ContractTokenId::get_type()),
+                    ("seed".to_string(), HashSha2256::get_type()),
+                    ("winners".to_string(), u32::get_type()),
+                ]),
+            ),
+        );
+        event_map.insert(
+            SEALED_BID_COMMITTED_EVENT_TAG,
+            (
+                "SealedBidCommitted".to_string(),
+                schema::Fields::Named(vec![
+                    ("token_id".to_string(), ContractTokenId::get_type()),
+                    ("bidder".to_string(), AccountAddress::get_type()),
+                    ("deposit".to_string(), Amount::get_type()),
+                ]),
+            ),
+        );
+        event_map.insert(
+            SEALED_BID_REVEALED_EVENT_TAG,
+            (
+                "SealedBidRevealed".to_string(),
+                schema::Fields::Named(vec![
+                    ("token_id".to_string(), ContractTokenId::get_type()),
+                    ("bidder".to_string(), AccountAddress::get_type()),
+                    ("amount".to_string(), Amount::get_type()),
+                ]),
+            ),
+        );
+        event_map.insert(
+            PROCEEDS_CREDITED_EVENT_TAG,
+            (
+                "ProceedsCredited".to_string(),
+                schema::Fields::Named(vec![
+                    ("token_id".to_string(), ContractTokenId::get_type()),
+                    ("recipient".to_string(), AccountAddress::get_type()),
+                    ("amount".to_string(), Amount::get_type()),
+                ]),
+            ),
+        );
+        event_map.insert(
+            PROCEEDS_WITHDRAWN_EVENT_TAG,
+            (
+                "ProceedsWithdrawn".to_string(),
+                schema::Fields::Named(vec![
+                    ("recipient".to_string(), AccountAddress::get_type()),
+                    ("amount".to_string(), Amount::get_type()),
+                ]),
+            ),
+        );
+        event_map.insert(
+            COLLECTION_METADATA_SET_EVENT_TAG,
+            (
+                "CollectionMetadataSet".to_string(),
+                schema::Fields::Named(vec![
+                    ("name".to_string(), String::get_type()),
+                    (
+                        "metadata".to_string(),
+                        schema::Type::Struct(schema::Fields::Named(vec![
+                            ("url".to_string(), schema::Type::String(schema::SizeLength::U16)),
+                            ("hash".to_string(), schema::Type::String(schema::SizeLength::U16)),
+                        ])),
+                    ),
+                ]),
+            ),
+        );
+        event_map.insert(
+            MAX_SUPPLY_UPDATED_EVENT_TAG,
+            (
+                "MaxSupplyUpdated".to_string(),
+                schema::Fields::Named(vec![
+                    ("token_id".to_string(), ContractTokenId::get_type()),
+                    ("max_supply".to_string(), ContractTokenAmount::get_type()),
+                ]),
+            ),
+        );
+        event_map.insert(
+            MINTING_CLOSED_EVENT_TAG,
+            (
+                "MintingClosed".to_string(),
+                schema::Fields::Named(vec![(
+                    "token_id".to_string(),
+                    ContractTokenId::get_type(),
+                )]),
+            ),
+        );
+        event_map.insert(
+            NONCE_EVENT_TAG,
+            (
+                "Nonce".to_string(),
+                schema::Fields::Named(vec![
+                    ("account".to_string(), AccountAddress::get_type()),
+                    ("nonce".to_string(), u64::get_type()),
+                ]),
+            ),
+        );
+        schema::Type::TaggedEnum(event_map)
+    }
+}
 
-    let params: MintParams = ctx.parameter_cursor().get()?;
+impl<S: HasStateApi> State<S> {
+    fn empty(
+        state_builder: &mut StateBuilder<S>,
+        admin: AccountAddress,
+        verify_key: PublicKeyEd25519,
+    ) -> Self {
+        let mut verify_keys = state_builder.new_set();
+        verify_keys.insert(verify_key);
 
-    let (state, builder) = host.state_and_builder();
+        State {
+            state_version: STATE_VERSION,
+            state: state_builder.new_map(),
+            tokens: state_builder.new_map(),
+            token_balance: state_builder.new_map(),
+            implementors: state_builder.new_map(),
+            verify_keys,
+            admin,
+            proposed_admin: None,
+            paused: false,
+            roles: state_builder.new_set(),
+            auctions: state_builder.new_map(),
+            lotteries: state_builder.new_map(),
+            sealed_bids: state_builder.new_map(),
+            proceeds: state_builder.new_map(),
+            pending_returns: state_builder.new_map(),
+            used_nonces: state_builder.new_set(),
+            holders: state_builder.new_map(),
+            minted: state_builder.new_map(),
+            collection_mints: state_builder.new_map(),
+            nonces: state_builder.new_map(),
+            locked_metadata: state_builder.new_set(),
+            next_token_id: 0,
+            collections: state_builder.new_map(),
+            minting_closed: state_builder.new_set(),
+            minters: state_builder.new_map(),
+        }
+    }
 
-    let verify = crypto_primitives.verify_ed25519_signature(
-        state.verify_key,
-        params.signature,
-        &sender_account.0,
-    );
+    fn mint(
+        &mut self,
+        token_id: &ContractTokenId,
+        amount: ContractTokenAmount,
+        owner: &Address,
+        state_builder: &mut StateBuilder<S>,
+    ) {
+        if amount == 0.into() {
+            return;
+        }
 
-    ensure!(verify, ContractError::Unauthorized);
+        self.credit(token_id, amount, owner, state_builder);
 
-    for token_id in params.tokens {
-        let token = state.tokens.get(&token_id);
+        let mut circulating = self
+            .token_balance
+            .entry(*token_id)
+            .or_insert_with(|| 0.into());
+        *circulating += amount;
+    }
 
-        ensure!(
-            token.is_some(),
-            ContractError::Custom(CustomContractError::AuctionNotInitialized)
-        );
+    fn burn(
+        &mut self,
+        token_id: &ContractTokenId,
+        amount: ContractTokenAmount,
+        owner: &Address,
+    ) -> ContractResult<()> {
+        self.debit(token_id, amount, owner)
+            .map_err(|_| ContractError::Custom(CustomContractError::NoBalanceToBurn))?;
 
-        let metadata_url = token.unwrap().0.to_metadata_url();
+        let mut circulating = self
+            .token_balance
+            .entry(*token_id)
+            .or_insert_with(|| 0.into());
+        *circulating -= amount;
 
-        let max_supply = state.get_token_supply(&token_id)?;
-        let circulating_supply = state.get_circulating_supply(&token_id)?;
+        Ok(())
+    }
 
-        ensure!(
-            max_supply >= circulating_supply + 1.into(),
-            ContractError::Custom(CustomContractError::MaxSupplyReached)
-        );
+    fn transfer(
+        &mut self,
+        token_id: &ContractTokenId,
+        amount: ContractTokenAmount,
+        from: &Address,
+        to: &Address,
+        state_builder: &mut StateBuilder<S>,
+    ) -> ContractResult<()> {
+        ensure!(self.contains_token(token_id), ContractError::InvalidTokenId);
 
-        state.mint(&token_id, &sender, builder);
+        if amount == 0.into() {
+            return Ok(());
+        }
 
-        logger.log(&Cis2Event::Mint(MintEvent {
-            token_id,
-            amount: TokenAmountU64::from(1),
-            owner: sender,
-        }))?;
+        self.debit(token_id, amount, from)?;
+        self.credit(token_id, amount, to, state_builder);
 
-        logger.log(&Cis2Event::TokenMetadata::<_, ContractTokenAmount>(
-            TokenMetadataEvent {
-                token_id,
-                metadata_url,
-            },
-        ))?;
+        Ok(())
     }
-    Ok(())
-}
 
-#[receive(
-    contract = "dino_auction",
-    name = "burn",
-    parameter = "BurnParams",
-    error = "ContractError",
-    enable_logger,
-    mutable
-)]
-fn contract_burn<S: HasStateApi>(
-    ctx: &impl HasReceiveContext,
-    host: &mut impl HasHost<State<S>, StateApiType = S>,
-    logger: &mut impl HasLogger,
-) -> ContractResult<()> {
-    let sender = ctx.sender();
+    /// Add `amount` of the token to the balance of `owner`.
+    fn credit(
+        &mut self,
+        token_id: &ContractTokenId,
+        amount: ContractTokenAmount,
+        owner: &Address,
+        state_builder: &mut StateBuilder<S>,
+    ) {
+        let mut owner_state = self
+            .state
+            .entry(*owner)
+            .or_insert_with(|| AddressState::empty(state_builder));
 
-    let params: BurnParams = ctx.parameter_cursor().get()?;
-    let token_id = params.token_id;
-    ensure!(
-        host.state().contains_token(&token_id),
-        ContractError::Custom(CustomContractError::AuctionNotInitialized));
+        *owner_state
+            .balances
+            .entry(*token_id)
+            .or_insert_with(|| 0.into()) += amount;
+        drop(owner_state);
 
-    let state = host.state_mut();
+        self.add_holder(token_id, owner, state_builder);
+    }
 
-    state.burn(&token_id, &sender)?;
+    /// Subtract `amount` of the token from the balance of `owner`, removing
+    /// the owner as holder once the balance reaches zero.
+    fn debit(
+        &mut self,
+        token_id: &ContractTokenId,
+        amount: ContractTokenAmount,
+        owner: &Address,
+    ) -> ContractResult<()> {
+        let mut owner_state = self
+            .state
+            .get_mut(owner)
+            .ok_or(ContractError::InsufficientFunds)?;
 
-    logger.log(&Cis2Event::Burn(BurnEvent {
-        token_id,
-        amount: TokenAmountU64::from(1),
-        owner: sender,
-    }))?;
+        let balance = owner_state
+            .balances
+            .get(token_id)
+            .map(|balance| *balance)
+            .ok_or(ContractError::InsufficientFunds)?;
+        ensure!(balance >= amount, ContractError::InsufficientFunds);
+
+        let remaining = balance - amount;
+        if remaining == 0.into() {
+            owner_state.balances.remove(token_id);
+            drop(owner_state);
+            self.remove_holder(token_id, owner);
+        } else {
+            owner_state.balances.insert(*token_id, remaining);
+        }
 
-    Ok(())
-}
+        Ok(())
+    }
 
-type TransferParameter = TransferParams<ContractTokenId, ContractTokenAmount>;
+    fn add_holder(
+        &mut self,
+        token_id: &ContractTokenId,
+        holder: &Address,
+        state_builder: &mut StateBuilder<S>,
+    ) {
+        self.holders
+            .entry(*token_id)
+            .or_insert_with(|| state_builder.new_set())
+            .insert(*holder);
+    }
 
-#[receive(
-    contract = "dino_auction",
-    name = "transfer",
-    parameter = "TransferParameter",
-    error = "ContractError",
-    enable_logger,
-    mutable
-)]
-fn contract_transfer<S: HasStateApi>(
-    ctx: &impl HasReceiveContext,
-    _host: &mut impl HasHost<State<S>, StateApiType = S>,
-    _logger: &mut impl HasLogger,
-) -> ContractResult<()> {
-    let _: TransferParameter = ctx.parameter_cursor().get()?;
-    bail!(ContractError::Unauthorized);
-}
-
-#[receive(
-    contract = "dino_auction",
-    name = "updateOperator",
-    parameter = "UpdateOperatorParams",
-    error = "ContractError",
-    enable_logger,
-    mutable
-)]
-fn contract_update_operator<S: HasStateApi>(
-    ctx: &impl HasReceiveContext,
-    host: &mut impl HasHost<State<S>, StateApiType = S>,
-    logger: &mut impl HasLogger,
-) -> ContractResult<()> {
-    let UpdateOperatorParams(params) = ctx.parameter_cursor().get()?;
-    let sender = ctx.sender();
-    let (state, builder) = host.state_and_builder();
-    for param in params {
-        match param.update {
-            OperatorUpdate::Add => state.add_operator(&sender, &param.operator, builder),
-            OperatorUpdate::Remove => state.remove_operator(&sender, &param.operator),
+    fn remove_holder(&mut self, token_id: &ContractTokenId, holder: &Address) {
+        if let Some(mut holders) = self.holders.get_mut(token_id) {
+            holders.remove(holder);
         }
+    }
 
-        logger.log(
-            &Cis2Event::<ContractTokenId, ContractTokenAmount>::UpdateOperator(
-                UpdateOperatorEvent {
-                    owner: sender,
-                    operator: param.operator,
-                    update: param.update,
-                },
-            ),
-        )?;
+    #[inline(always)]
+    fn has_holders(&self, token_id: &ContractTokenId) -> bool {
+        self.holders.get(token_id).is_some()
     }
 
-    Ok(())
-}
+    /// Remove the token from the balances of at most `limit` of its holders.
+    /// Returns the purged holders with the balance each of them held.
+    fn purge_holders(
+        &mut self,
+        token_id: &ContractTokenId,
+        limit: u32,
+    ) -> Vec<(Address, ContractTokenAmount)> {
+        let batch: Vec<Address> = match self.holders.get(token_id) {
+            Some(holders) => holders.iter().take(limit as usize).map(|h| *h).collect(),
+            None => return Vec::new(),
+        };
 
-#[receive(
-    contract = "dino_auction",
-    name = "operatorOf",
-    parameter = "OperatorOfQueryParams",
-    return_value = "OperatorOfQueryResponse",
-    error = "ContractError"
-)]
-fn contract_operator_of<S: HasStateApi>(
-    ctx: &impl HasReceiveContext,
-    host: &impl HasHost<State<S>, StateApiType = S>,
-) -> ContractResult<OperatorOfQueryResponse> {
-    let params: OperatorOfQueryParams = ctx.parameter_cursor().get()?;
-    let mut response = Vec::with_capacity(params.queries.len());
-    for query in params.queries {
-        let is_operator = host.state().is_operator(&query.address, &query.owner);
-        response.push(is_operator);
-    }
-    let result = OperatorOfQueryResponse::from(response);
-    Ok(result)
-}
+        let mut purged = Vec::with_capacity(batch.len());
+        for holder in batch {
+            if let Some(mut address_state) = self.state.get_mut(&holder) {
+                if let Some(balance) = address_state.balances.remove_and_get(token_id) {
+                    purged.push((holder, balance));
+                }
+            }
+            self.remove_holder(token_id, &holder);
+        }
 
-type ContractBalanceOfQueryParams = BalanceOfQueryParams<ContractTokenId>;
-type ContractBalanceOfQueryResponse = BalanceOfQueryResponse<ContractTokenAmount>;
+        let empty = self
+            .holders
+            .get(token_id)
+            .is_some_and(|holders| holders.iter().next().is_none());
+        if empty {
+            self.holders.remove(token_id);
+        }
 
-#[receive(
-    contract = "dino_auction",
-    name = "balanceOf",
-    parameter = "ContractBalanceOfQueryParams",
-    return_value = "ContractBalanceOfQueryResponse",
-    error = "ContractError"
-)]
-fn contract_balance_of<S: HasStateApi>(
-    ctx: &impl HasReceiveContext,
-    host: &impl HasHost<State<S>, StateApiType = S>,
-) -> ContractResult<ContractBalanceOfQueryResponse> {
-    let params: ContractBalanceOfQueryParams = ctx.parameter_cursor().get()?;
-    let mut response = Vec::with_capacity(params.queries.len());
-    for query in params.queries {
-        let amount = host.state().balance(&query.token_id, &query.address)?;
-        response.push(amount);
+        purged
     }
-    let result = ContractBalanceOfQueryResponse::from(response);
-    Ok(result)
-}
 
-type ContractTokenMetadataQueryParams = TokenMetadataQueryParams<ContractTokenId>;
+    #[inline(always)]
+    fn has_minters(&self, token_id: &ContractTokenId) -> bool {
+        self.minters.get(token_id).is_some()
+    }
 
-#[receive(
-    contract = "dino_auction",
-    name = "tokenMetadata",
-    parameter = "ContractTokenMetadataQueryParams",
-    return_value = "TokenMetadataQueryResponse",
-    error = "ContractError"
-)]
-fn contract_token_metadata<S: HasStateApi>(
-    ctx: &impl HasReceiveContext,
-    host: &impl HasHost<State<S>, StateApiType = S>,
-) -> ContractResult<TokenMetadataQueryResponse> {
-    let params: ContractTokenMetadataQueryParams = ctx.parameter_cursor().get()?;
-    let mut response = Vec::with_capacity(params.queries.len());
-    for token_id in params.queries {
-        ensure!(
-            host.state().contains_token(&token_id),
-            ContractError::InvalidTokenId
-        );
+    /// Clear the mint counters of at most `limit` of the accounts that minted
+    /// the token. Returns the number of accounts cleared.
+    fn purge_minters(&mut self, token_id: &ContractTokenId, limit: u32) -> u32 {
+        let (collection, batch): (Option<String>, Vec<AccountAddress>) =
+            match self.minters.get(token_id) {
+                Some(minters) => (
+                    minters.collection.clone(),
+                    minters.accounts.iter().take(limit as usize).map(|a| *a).collect(),
+                ),
+                None => return 0,
+            };
+
+        for account in batch.iter() {
+            self.minted.remove(&(*account, *token_id));
+            if let Some(name) = &collection {
+                let key = (*account, name.clone());
+                let mut cleared = false;
+                if let Some(mut taken) = self.collection_mints.get_mut(&key) {
+                    *taken = taken.saturating_sub(1);
+                    cleared = *taken == 0;
+                }
+                if cleared {
+                    self.collection_mints.remove(&key);
+                }
+            }
+            if let Some(mut minters) = self.minters.get_mut(token_id) {
+                minters.accounts.remove(account);
+            }
+        }
 
-        let meta = match host.state().tokens.get(&token_id) {
-            Some(url) => url,
-            None => return Err(ContractError::InvalidTokenId),
-        };
+        let empty = self
+            .minters
+            .get(token_id)
+            .is_some_and(|minters| minters.accounts.iter().next().is_none());
+        if empty {
+            self.minters.remove(token_id);
+        }
 
-        response.push(meta.0.to_metadata_url());
+        batch.len() as u32
     }
-    let result = TokenMetadataQueryResponse::from(response);
-    Ok(result)
-}
 
-#[receive(
-    contract = "dino_auction",
-    name = "supports",
-    parameter = "SupportsQueryParams",
-    return_value = "SupportsQueryResponse",
-    error = "ContractError"
-)]
-fn contract_supports<S: HasStateApi>(
-    ctx: &impl HasReceiveContext,
-    host: &impl HasHost<State<S>, StateApiType = S>,
-) -> ContractResult<SupportsQueryResponse> {
-    let params: SupportsQueryParams = ctx.parameter_cursor().get()?;
+    #[inline(always)]
+    fn contains_token(&self, token_id: &ContractTokenId) -> bool {
+        self.tokens.get(token_id).is_some()
+    }
 
-    let mut response = Vec::with_capacity(params.queries.len());
-    for std_id in params.queries {
-        if SUPPORTS_STANDARDS.contains(&std_id.as_standard_identifier()) {
-            response.push(SupportResult::Support);
-        } else {
-            response.push(host.state().have_implementors(&std_id));
-        }
+    fn balance(
+        &self,
+        token_id: &ContractTokenId,
+        address: &Address,
+    ) -> ContractResult<ContractTokenAmount> {
+        ensure!(self.contains_token(token_id), ContractError::InvalidTokenId);
+        let balance = self.state.get(address).map_or(0.into(), |address_state| {
+            address_state.balances.get(token_id).map_or(0.into(), |balance| *balance)
+        });
+        Ok(balance)
     }
-    let result = SupportsQueryResponse::from(response);
-    Ok(result)
-}
 
-#[receive(
-    contract = "dino_auction",
-    name = "setImplementors",
-    parameter = "SetImplementorsParams",
-    error = "ContractError",
-    mutable
-)]
-fn contract_set_implementor<S: HasStateApi>(
-    ctx: &impl HasReceiveContext,
-    host: &mut impl HasHost<State<S>, StateApiType = S>,
-) -> ContractResult<()> {
-    ensure!(
-        ctx.sender().matches_account(&ctx.owner()),
-        ContractError::Unauthorized
-    );
+    fn is_operator(&self, address: &Address, owner: &Address) -> bool {
+        self.state
+            .get(owner)
+            .map(|address_state| address_state.operators.contains(address))
+            .unwrap_or(false)
+    }
 
-    let params: SetImplementorsParams = ctx.parameter_cursor().get()?;
-    host.state_mut()
-        .set_implementors(params.id, params.implementors);
-    Ok(())
-}
+    fn add_operator(
+        &mut self,
+        owner: &Address,
+        operator: &Address,
+        state_builder: &mut StateBuilder<S>,
+    ) {
+        let mut owner_state = self
+            .state
+            .entry(*owner)
+            .or_insert_with(|| AddressState::empty(state_builder));
+        owner_state.operators.insert(*operator);
+    }
 
-#[derive(Serial, Deserial, SchemaType)]
-struct AuctionInitParams {
-    tokens: collections::BTreeMap<ContractTokenId, (TokenMetadata, ContractTokenAmount)>,
-}
+    fn remove_operator(&mut self, owner: &Address, operator: &Address) {
+        self.state.entry(*owner).and_modify(|address_state| {
+            address_state.operators.remove(operator);
+        });
+    }
 
-#[receive(
-    contract = "dino_auction",
-    name = "init_auction",
-    parameter = "AuctionInitParams",
-    error = "ContractError",
-    mutable
-)]
-fn contract_init_auction<S: HasStateApi>(
-    ctx: &impl HasReceiveContext,
-    host: &mut impl HasHost<State<S>, StateApiType = S>,
-) -> ContractResult<()> {
-    let owner = ctx.owner();
-    let sender = ctx.sender();
+    fn verify_with_any_key(
+        &self,
+        crypto_primitives: &impl HasCryptoPrimitives,
+        signature: SignatureEd25519,
+        message: &[u8],
+    ) -> bool {
+        self.verify_keys
+            .iter()
+            .any(|key| crypto_primitives.verify_ed25519_signature(*key, signature, message))
+    }
 
-    ensure!(sender.matches_account(&owner), ContractError::Unauthorized);
+    fn add_verify_key(&mut self, key: PublicKeyEd25519) -> ContractResult<()> {
+        ensure!(
+            self.verify_keys.insert(key),
+            ContractError::Custom(CustomContractError::VerifyKeyAlreadyActive)
+        );
+        Ok(())
+    }
 
-    let params: AuctionInitParams = ctx.parameter_cursor().get()?;
+    fn revoke_verify_key(&mut self, key: &PublicKeyEd25519) -> ContractResult<()> {
+        ensure!(
+            self.verify_keys.remove(key),
+            ContractError::Custom(CustomContractError::VerifyKeyNotActive)
+        );
+        Ok(())
+    }
 
-    let state = host.state_mut();
+    fn has_role(&self, address: &Address, role: Role) -> bool {
+        address.matches_account(&self.admin) || self.roles.contains(&(*address, role))
+    }
 
-    for (token_id, token_info) in params.tokens {
-        let contains = state.contains_token(&token_id);
+    fn ensure_role(&self, address: &Address, role: Role) -> ContractResult<()> {
+        ensure!(self.has_role(address, role), ContractError::Unauthorized);
+        Ok(())
+    }
 
+    fn ensure_not_paused(&self) -> ContractResult<()> {
         ensure!(
-            !contains,
-            ContractError::Custom(CustomContractError::TokenAlreadyCreated)
+            !self.paused,
+            ContractError::Custom(CustomContractError::Paused)
         );
+        Ok(())
+    }
 
-        state.tokens.insert(token_id, token_info);
+    fn grant_role(&mut self, address: Address, role: Role) -> ContractResult<()> {
+        ensure!(
+            role != Role::Admin,
+            ContractError::Custom(CustomContractError::InvalidRole)
+        );
+        self.roles.insert((address, role));
+        Ok(())
     }
 
-    Ok(())
-}
+    fn revoke_role(&mut self, address: Address, role: Role) -> ContractResult<()> {
+        ensure!(
+            role != Role::Admin,
+            ContractError::Custom(CustomContractError::InvalidRole)
+        );
+        self.roles.remove(&(address, role));
+        Ok(())
+    }
 
-#[derive(Serial, Deserial, SchemaType)]
-struct ActionBurnParams {
-    tokens: collections::BTreeSet<ContractTokenId>,
-}
+    fn have_implementors(&self, std_id: &StandardIdentifierOwned) -> SupportResult {
+        if let Some(addresses) = self.implementors.get(std_id) {
+            SupportResult::SupportBy(addresses.to_vec())
+        } else {
+            SupportResult::NoSupport
+        }
+    }
 
-#[receive(
-    contract = "dino_auction",
-    name = "burn_auction",
-    parameter = "ActionBurnParams",
-    error = "ContractError",
-    mutable
-)]
-fn contract_burn_auction<S: HasStateApi>(
-    ctx: &impl HasReceiveContext,
-    host: &mut impl HasHost<State<S>, StateApiType = S>,
-) -> ContractResult<()> {
-    let owner = ctx.owner();
-    let sender = ctx.sender();
+    fn set_implementors(
+        &mut self,
+        std_id: StandardIdentifierOwned,
+        implementors: Vec<ContractAddress>,
+    ) {
+        self.implementors.insert(std_id, implementors);
+    }
+
+    fn get_token_supply(&self, token_id: &ContractTokenId) -> ContractResult<ContractTokenAmount> {
+        ensure!(self.contains_token(token_id), ContractError::InvalidTokenId);
+        let supply = self
+            .tokens
+            .get(token_id)
+            .map(|info| info.max_supply)
+            .map_or(0.into(), |v| v);
+        Ok(supply)
+    }
+
+    fn get_circulating_supply(
+        &self,
+        token_id: &ContractTokenId,
+    ) -> ContractResult<ContractTokenAmount> {
+        ensure!(self.contains_token(token_id), ContractError::InvalidTokenId);
+        let circulating = self.token_balance.get(token_id).map_or(0.into(), |v| *v);
+        Ok(circulating)
+    }
+
+    /// Record that `account` minted `amount` of the token, failing if this
+    /// takes the account over the caps of the token or its collection.
+    fn record_mint(
+        &mut self,
+        account: AccountAddress,
+        token_id: &ContractTokenId,
+        amount: ContractTokenAmount,
+        token: &TokenInfo,
+        state_builder: &mut StateBuilder<S>,
+    ) -> ContractResult<()> {
+        let mut minted = self
+            .minted
+            .entry((account, *token_id))
+            .or_insert_with(|| 0.into());
+        let first_mint = *minted == 0.into();
+        ensure!(
+            *minted + amount <= token.max_per_account,
+            ContractError::Custom(CustomContractError::AccountCapReached)
+        );
+        *minted += amount;
+        drop(minted);
+
+        if let (true, Some(cap)) = (first_mint, &token.collection) {
+            let max_tokens = self
+                .collections
+                .get(&cap.name)
+                .and_then(|collection| collection.max_tokens_per_account);
+            let mut taken = self
+                .collection_mints
+                .entry((account, cap.name.clone()))
+                .or_insert(0);
+            ensure!(
+                max_tokens.is_none_or(|max| *taken < max),
+                ContractError::Custom(CustomContractError::CollectionCapReached)
+            );
+            *taken += 1;
+        }
+
+        if first_mint {
+            self.minters
+                .entry(*token_id)
+                .or_insert_with(|| Minters {
+                    collection: token.collection.as_ref().map(|cap| cap.name.clone()),
+                    accounts: state_builder.new_set(),
+                })
+                .accounts
+                .insert(account);
+        }
+
+        Ok(())
+    }
+
+    /// Units of the token held back for the highest bidder of an auction that
+    /// is not closed yet.
+    fn reserved_supply(&self, token_id: &ContractTokenId) -> ContractTokenAmount {
+        let open = self
+            .auctions
+            .get(token_id)
+            .is_some_and(|auction| !auction.closed && auction.highest_bidder.is_some());
+        if open {
+            1.into()
+        } else {
+            0.into()
+        }
+    }
+
+    /// Fail if minting of the token is closed or `amount` more, on top of the
+    /// units reserved for an open auction, would exceed its max supply.
+    fn ensure_supply_left(
+        &self,
+        token_id: &ContractTokenId,
+        amount: ContractTokenAmount,
+    ) -> ContractResult<()> {
+        self.ensure_unreserved_supply_left(token_id, amount + self.reserved_supply(token_id))
+    }
+
+    /// Fail if minting of the token is closed or no unit is left for the
+    /// winner of its auction. A new bid takes over the unit reserved for the
+    /// current highest bidder.
+    fn ensure_auction_unit_left(&self, token_id: &ContractTokenId) -> ContractResult<()> {
+        self.ensure_unreserved_supply_left(token_id, 1.into())
+    }
+
+    fn ensure_unreserved_supply_left(
+        &self,
+        token_id: &ContractTokenId,
+        amount: ContractTokenAmount,
+    ) -> ContractResult<()> {
+        ensure!(
+            !self.minting_closed.contains(token_id),
+            ContractError::Custom(CustomContractError::MintingClosed)
+        );
+        let max_supply = self.get_token_supply(token_id)?;
+        let circulating_supply = self.get_circulating_supply(token_id)?;
+
+        ensure!(
+            max_supply >= circulating_supply + amount,
+            ContractError::Custom(CustomContractError::MaxSupplyReached)
+        );
+        Ok(())
+    }
+
+    fn add_pending_return(&mut self, account: AccountAddress, amount: Amount) {
+        let mut pending = self
+            .pending_returns
+            .entry(account)
+            .or_insert_with(Amount::zero);
+        *pending += amount;
+    }
+
+    fn take_pending_return(&mut self, account: &AccountAddress) -> Amount {
+        self.pending_returns
+            .remove_and_get(account)
+            .unwrap_or_else(Amount::zero)
+    }
+
+
+    fn place_bid(
+        &mut self,
+        token_id: &ContractTokenId,
+        bidder: AccountAddress,
+        amount: Amount,
+    ) -> ContractResult<()> {
+        let mut auction = self
+            .auctions
+            .entry(*token_id)
+            .or_default();
+
+        ensure!(
+            !auction.closed,
+            ContractError::Custom(CustomContractError::AuctionClosed)
+        );
+        ensure!(
+            amount > auction.highest_bid,
+            ContractError::Custom(CustomContractError::BidTooLow)
+        );
+
+        let outbid = auction
+            .highest_bidder
+            .replace(bidder)
+            .map(|previous| (previous, auction.highest_bid));
+        auction.highest_bid = amount;
+        drop(auction);
+
+        if let Some((previous, previous_bid)) = outbid {
+            self.add_pending_return(previous, previous_bid);
+        }
+        Ok(())
+    }
+
+    fn commit_sealed_bid(
+        &mut self,
+        token_id: &ContractTokenId,
+        bidder: AccountAddress,
+        bid: SealedBid,
+        state_builder: &mut StateBuilder<S>,
+    ) -> ContractResult<()> {
+        let previous = self
+            .sealed_bids
+            .entry(*token_id)
+            .or_insert_with(|| state_builder.new_map())
+            .insert(bidder, bid);
+        ensure!(
+            previous.is_none(),
+            ContractError::Custom(CustomContractError::BidAlreadyCommitted)
+        );
+        Ok(())
+    }
+
+    fn take_sealed_bid(
+        &mut self,
+        token_id: &ContractTokenId,
+        bidder: &AccountAddress,
+    ) -> ContractResult<SealedBid> {
+        let mut bids = self
+            .sealed_bids
+            .get_mut(token_id)
+            .ok_or(ContractError::Custom(CustomContractError::BidNotCommitted))?;
+        let bid = bids
+            .remove_and_get(bidder)
+            .ok_or(ContractError::Custom(CustomContractError::BidNotCommitted))?;
+        let empty = bids.iter().next().is_none();
+        drop(bids);
+
+        if empty {
+            self.sealed_bids.remove(token_id);
+        }
+        Ok(bid)
+    }
+
+    /// Place a revealed sealed bid, returning the deposit above the bid, or
+    /// all of it if the bid is not the highest.
+    fn place_revealed_bid(
+        &mut self,
+        token_id: &ContractTokenId,
+        bidder: AccountAddress,
+        amount: Amount,
+        deposit: Amount,
+    ) -> ContractResult<()> {
+        let highest_bid = self
+            .auctions
+            .get(token_id)
+            .map_or(Amount::zero(), |auction| auction.highest_bid);
+
+        // A bid revealed after `closeMinting` can not win, so its deposit is
+        // returned in full instead of opening the auction again.
+        let open = !self.minting_closed.contains(token_id);
+        let refund = if open && amount > highest_bid {
+            self.place_bid(token_id, bidder, amount)?;
+            deposit - amount
+        } else {
+            deposit
+        };
+
+        if refund > Amount::zero() {
+            self.add_pending_return(bidder, refund);
+        }
+        Ok(())
+    }
+
+    fn close_auction(
+        &mut self,
+        token_id: &ContractTokenId,
+    ) -> ContractResult<Option<(AccountAddress, Amount)>> {
+        let mut auction = self
+            .auctions
+            .entry(*token_id)
+            .or_default();
+
+        ensure!(
+            !auction.closed,
+            ContractError::Custom(CustomContractError::AuctionClosed)
+        );
+        auction.closed = true;
+
+        Ok(auction
+            .highest_bidder
+            .map(|bidder| (bidder, auction.highest_bid)))
+    }
+
+    /// Validate and store a new auction, adding the token to its collection.
+    fn create_auction(
+        &mut self,
+        token_id: ContractTokenId,
+        token_info: TokenInfo,
+        state_builder: &mut StateBuilder<S>,
+    ) -> ContractResult<()> {
+        ensure!(
+            !self.contains_token(&token_id),
+            ContractError::Custom(CustomContractError::TokenAlreadyCreated)
+        );
+        ensure!(
+            !self.has_holders(&token_id) && !self.has_minters(&token_id),
+            ContractError::Custom(CustomContractError::HoldersNotPurged)
+        );
+        ensure!(
+            self.sealed_bids.get(&token_id).is_none(),
+            ContractError::Custom(CustomContractError::SealedBidsNotSettled)
+        );
+        token_info.ensure_valid_window()?;
+        token_info.ensure_valid_sealed_bid()?;
+        token_info.ensure_valid_price()?;
+        token_info.ensure_valid_payouts()?;
+        token_info.metadata.ensure_valid_hash()?;
+
+        if let Some(cap) = &token_info.collection {
+            let mut collection = self
+                .collections
+                .entry(cap.name.clone())
+                .or_insert_with(|| Collection::empty(state_builder));
+            if let Some(max_tokens) = cap.max_tokens_per_account {
+                ensure!(
+                    collection.max_tokens_per_account.is_none_or(|max| max == max_tokens),
+                    ContractError::Custom(CustomContractError::CollectionCapMismatch)
+                );
+                collection.max_tokens_per_account = Some(max_tokens);
+            }
+            collection.tokens.insert(token_id);
+        }
+        self.tokens.insert(token_id, token_info);
+        Ok(())
+    }
+
+    /// Take the next id from the counter that is free to create a token with.
+    fn next_free_token_id(&mut self) -> ContractTokenId {
+        loop {
+            let token_id = TokenIdU32(self.next_token_id);
+            self.next_token_id += 1;
+            if !self.contains_token(&token_id)
+                && !self.has_holders(&token_id)
+                && !self.has_minters(&token_id)
+                && self.sealed_bids.get(&token_id).is_none()
+            {
+                return token_id;
+            }
+        }
+    }
+
+    fn remove_from_collection(&mut self, token_id: &ContractTokenId, token_info: &TokenInfo) {
+        if let Some(cap) = &token_info.collection {
+            if let Some(mut collection) = self.collections.get_mut(&cap.name) {
+                collection.tokens.remove(token_id);
+            }
+        }
+    }
+
+    fn remove_auction(&mut self, token_id: &ContractTokenId) {
+        let removed = self.auctions.remove_and_get(token_id);
+        if let Some(AuctionState {
+            highest_bid,
+            highest_bidder: Some(bidder),
+            closed: false,
+        }) = removed
+        {
+            self.add_pending_return(bidder, highest_bid);
+        }
+    }
+}
+
+/// Split `amount` between the payout recipients of the token, crediting the
+/// rounding remainder to the last one, or all of it to the admin if the token
+/// has no payouts.
+fn credit_proceeds<S: HasStateApi>(
+    state: &mut State<S>,
+    logger: &mut impl HasLogger,
+    token_id: ContractTokenId,
+    payouts: &[Payout],
+    amount: Amount,
+) -> ContractResult<()> {
+    if amount == Amount::zero() {
+        return Ok(());
+    }
+
+    let mut credits = Vec::new();
+    let mut remaining = amount;
+    if let Some((last, rest)) = payouts.split_last() {
+        for payout in rest {
+            let share = u128::from(amount.micro_ccd) * u128::from(payout.share_bps) / 10_000;
+            let share = Amount::from_micro_ccd(share as u64);
+            credits.push((payout.recipient, share));
+            remaining -= share;
+        }
+        credits.push((last.recipient, remaining));
+    } else {
+        credits.push((state.admin, amount));
+    }
+
+    for (recipient, amount) in credits {
+        *state.proceeds.entry(recipient).or_insert_with(Amount::zero) += amount;
+
+        logger.log(&ContractEvent::ProceedsCredited(ProceedsCreditedEvent {
+            token_id,
+            recipient,
+            amount,
+        }))?;
+    }
+    Ok(())
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+struct InitParams {
+    verify_key: PublicKeyEd25519,
+}
+
+#[init(
+    contract = "dino_auction",
+    parameter = "InitParams",
+    event = "ContractEvent"
+)]
+fn contract_init<S: HasStateApi>(
+    ctx: &impl HasInitContext,
+    state_builder: &mut StateBuilder<S>,
+) -> InitResult<State<S>> {
+    let params: InitParams = ctx.parameter_cursor().get()?;
+
+    Ok(State::empty(state_builder, ctx.init_origin(), params.verify_key))
+}
+
+#[derive(Serialize, SchemaType)]
+struct ViewAddressState {
+    balances: Vec<(ContractTokenId, ContractTokenAmount)>,
+    operators: Vec<Address>,
+}
+
+impl ViewAddressState {
+    fn from_state<S: HasStateApi>(state: &AddressState<S>) -> Self {
+        ViewAddressState {
+            balances: state
+                .balances
+                .iter()
+                .map(|(token_id, amount)| (*token_id, *amount))
+                .collect(),
+            operators: state.operators.iter().map(|operator| *operator).collect(),
+        }
+    }
+}
+
+#[derive(Serialize, SchemaType)]
+struct ViewState {
+    state: Vec<(Address, ViewAddressState)>,
+    tokens: Vec<ContractTokenId>,
+    paused: bool,
+    state_version: u32,
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "view",
+    return_value = "ViewState"
+)]
+fn contract_view<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<ViewState> {
+    let state = host.state();
+
+    let mut inner_state = Vec::new();
+    for (k, a_state) in state.state.iter() {
+        inner_state.push((*k, ViewAddressState::from_state(&a_state)));
+    }
+    let mut tokens = Vec::new();
+    for v in state.tokens.iter() {
+        tokens.push(*v.0);
+    }
+
+    Ok(ViewState {
+        state: inner_state,
+        tokens,
+        paused: state.paused,
+        state_version: state.state_version,
+    })
+}
+
+/// Page of a view. `cursor` is the number of entries to skip and `limit` the
+/// maximum number of entries to return. The skipped entries are still walked,
+/// so the cost of a page grows with its cursor.
+#[derive(Serialize, SchemaType)]
+struct ViewPageParams {
+    cursor: u32,
+    limit: u32,
+}
+
+#[derive(Serialize, SchemaType)]
+struct ViewHoldersResponse {
+    holders: Vec<(Address, ViewAddressState)>,
+    /// Cursor of the next page, `None` when there are no more holders.
+    next: Option<u32>,
+}
+
+#[derive(Serialize, SchemaType)]
+struct ViewToken {
+    token_id: ContractTokenId,
+    max_supply: ContractTokenAmount,
+    circulating_supply: ContractTokenAmount,
+    metadata_locked: bool,
+}
+
+#[derive(Serialize, SchemaType)]
+struct ViewTokensResponse {
+    tokens: Vec<ViewToken>,
+    /// Cursor of the next page, `None` when there are no more tokens.
+    next: Option<u32>,
+}
+
+/// Collect at most `limit` items after skipping `cursor`, together with the
+/// cursor of the next page if any items are left.
+fn paginate<T>(items: impl Iterator<Item = T>, params: &ViewPageParams) -> (Vec<T>, Option<u32>) {
+    let mut items = items.skip(params.cursor as usize).peekable();
+    let mut page = Vec::new();
+    while page.len() < params.limit as usize {
+        match items.next() {
+            Some(item) => page.push(item),
+            None => break,
+        }
+    }
+    let next = items
+        .peek()
+        .map(|_| params.cursor + page.len() as u32);
+    (page, next)
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "viewHolders",
+    parameter = "ViewPageParams",
+    return_value = "ViewHoldersResponse",
+    error = "ContractError"
+)]
+fn contract_view_holders<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<ViewHoldersResponse> {
+    let params: ViewPageParams = ctx.parameter_cursor().get()?;
+
+    let holders = host
+        .state()
+        .state
+        .iter()
+        .filter(|(_, a_state)| a_state.balances.iter().next().is_some())
+        .map(|(address, a_state)| (*address, ViewAddressState::from_state(&a_state)));
+    let (holders, next) = paginate(holders, &params);
+
+    Ok(ViewHoldersResponse { holders, next })
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "viewTokens",
+    parameter = "ViewPageParams",
+    return_value = "ViewTokensResponse",
+    error = "ContractError"
+)]
+fn contract_view_tokens<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<ViewTokensResponse> {
+    let params: ViewPageParams = ctx.parameter_cursor().get()?;
+    let state = host.state();
+
+    let tokens = state.tokens.iter().map(|(token_id, info)| ViewToken {
+        token_id: *token_id,
+        max_supply: info.max_supply,
+        circulating_supply: state.token_balance.get(&token_id).map_or(0.into(), |v| *v),
+        metadata_locked: state.locked_metadata.contains(&token_id),
+    });
+    let (tokens, next) = paginate(tokens, &params);
+
+    Ok(ViewTokensResponse { tokens, next })
+}
+
+#[derive(Serialize, SchemaType)]
+struct ViewCollectionParams {
+    name: String,
+    page: ViewPageParams,
+}
+
+#[derive(Serialize, SchemaType)]
+struct ViewCollectionResponse {
+    metadata: Option<TokenMetadata>,
+    max_tokens_per_account: Option<u32>,
+    tokens: Vec<ContractTokenId>,
+    /// Cursor of the next page, `None` when there are no more tokens.
+    next: Option<u32>,
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "viewCollection",
+    parameter = "ViewCollectionParams",
+    return_value = "ViewCollectionResponse",
+    error = "ContractError"
+)]
+fn contract_view_collection<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<ViewCollectionResponse> {
+    let params: ViewCollectionParams = ctx.parameter_cursor().get()?;
+
+    let collection = host
+        .state()
+        .collections
+        .get(&params.name)
+        .ok_or(ContractError::Custom(CustomContractError::CollectionNotFound))?;
+    let (tokens, next) = paginate(collection.tokens.iter().map(|token_id| *token_id), &params.page);
+
+    Ok(ViewCollectionResponse {
+        metadata: collection.metadata.clone(),
+        max_tokens_per_account: collection.max_tokens_per_account,
+        tokens,
+        next,
+    })
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "viewAccount",
+    parameter = "Address",
+    return_value = "ViewAddressState",
+    error = "ContractError"
+)]
+fn contract_view_account<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<ViewAddressState> {
+    let address: Address = ctx.parameter_cursor().get()?;
+
+    let view = host
+        .state()
+        .state
+        .get(&address)
+        .map(|a_state| ViewAddressState::from_state(&a_state))
+        .unwrap_or(ViewAddressState {
+            balances: Vec::new(),
+            operators: Vec::new(),
+        });
+
+    Ok(view)
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "mint",
+    crypto_primitives,
+    parameter = "MintParams",
+    error = "ContractError",
+    enable_logger,
+    payable,
+    mutable
+)]
+fn contract_mint<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    payment: Amount,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<()> {
+    let params: MintParams = ctx.parameter_cursor().get()?;
+    mint_tokens(ctx, host, logger, crypto_primitives, ctx.sender(), params, payment)
+}
+
+/// Mint the tokens to `sender`, paying the current price of each token from
+/// `payment` and refunding whatever is left.
+fn mint_tokens<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+    sender: Address,
+    params: MintParams,
+    payment: Amount,
+) -> ContractResult<()> {
+    host.state().ensure_not_paused()?;
+
+    let sender_account = match sender {
+        Address::Account(a) => a,
+        Address::Contract(_) => bail!(ContractError::Custom(CustomContractError::AccountOnly)),
+    };
+    let owner = params.to.as_ref().map_or(sender, Receiver::address);
+
+    authorize_account(
+        ctx,
+        host.state_mut(),
+        crypto_primitives,
+        sender_account,
+        &params.authorization,
+    )?;
+
+    let mut paid = Amount::zero();
+    for (token_id, amount) in params.tokens {
+        let (state, builder) = host.state_and_builder();
+
+        let token = state.tokens.get(&token_id);
+
+        ensure!(
+            token.is_some(),
+            ContractError::Custom(CustomContractError::AuctionNotInitialized)
+        );
+
+        let token = token.unwrap();
+        ensure_token_authorized(
+            crypto_primitives,
+            &sender_account,
+            &params.authorization,
+            &token_id,
+            &token,
+        )?;
+        ensure!(
+            token.sealed_bid.is_none(),
+            ContractError::Custom(CustomContractError::SealedBidAuction)
+        );
+        match state.lotteries.get_mut(&token_id) {
+            Some(mut lottery) => lottery.claim(&sender_account, amount)?,
+            None => token.ensure_open(ctx.metadata().slot_time())?,
+        }
+
+        let price = token
+            .current_price(ctx.metadata().slot_time())
+            .micro_ccd
+            .checked_mul(amount.0)
+            .map(Amount::from_micro_ccd)
+            .ok_or(ContractError::Custom(CustomContractError::PriceOverflow))?;
+
+        let metadata_url = token.metadata.to_metadata_url();
+        let token = token.clone();
+
+        state.ensure_supply_left(&token_id, amount)?;
+        state.record_mint(sender_account, &token_id, amount, &token, builder)?;
+
+        state.mint(&token_id, amount, &owner, builder);
+
+        logger.log(&ContractEvent::Cis2(Cis2Event::Mint(MintEvent {
+            token_id,
+            amount,
+            owner,
+        })))?;
+
+        logger.log(&ContractEvent::Cis2(Cis2Event::TokenMetadata(
+            TokenMetadataEvent {
+                token_id,
+                metadata_url,
+            },
+        )))?;
+
+        credit_proceeds(state, logger, token_id, &token.payouts, price)?;
+        paid = paid
+            .checked_add(price)
+            .ok_or(ContractError::Custom(CustomContractError::PriceOverflow))?;
+
+        if let Some(Receiver::Contract(address, function)) = &params.to {
+            let parameter = OnReceivingCis2Params {
+                token_id,
+                amount,
+                from: Address::Contract(ctx.self_address()),
+                data: AdditionalData::empty(),
+            };
+            host.invoke_contract(
+                address,
+                &parameter,
+                function.as_entrypoint_name(),
+                Amount::zero(),
+            )?;
+        }
+    }
+
+    ensure!(
+        payment >= paid,
+        ContractError::Custom(CustomContractError::InsufficientPayment)
+    );
+
+    let refund = payment - paid;
+    if refund > Amount::zero() {
+        host.invoke_transfer(&sender_account, refund)?;
+    }
+    Ok(())
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "burn",
+    parameter = "BurnParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+fn contract_burn<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let params: BurnParams = ctx.parameter_cursor().get()?;
+    burn_tokens(host, logger, ctx.sender(), params)
+}
+
+fn burn_tokens<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    sender: Address,
+    params: BurnParams,
+) -> ContractResult<()> {
+    host.state().ensure_not_paused()?;
+
+    let token_id = params.token_id;
+    ensure!(
+        host.state().contains_token(&token_id),
+        ContractError::Custom(CustomContractError::AuctionNotInitialized));
+
+    let state = host.state_mut();
+
+    state.burn(&token_id, params.amount, &sender)?;
+
+    logger.log(&ContractEvent::Cis2(Cis2Event::Burn(BurnEvent {
+        token_id,
+        amount: params.amount,
+        owner: sender,
+    })))?;
+
+    Ok(())
+}
+
+type TransferParameter = TransferParams<ContractTokenId, ContractTokenAmount>;
+
+#[receive(
+    contract = "dino_auction",
+    name = "transfer",
+    parameter = "TransferParameter",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+fn contract_transfer<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let params: TransferParameter = ctx.parameter_cursor().get()?;
+    transfer_tokens(host, logger, ctx.sender(), params)
+}
+
+fn transfer_tokens<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    sender: Address,
+    TransferParams(transfers): TransferParameter,
+) -> ContractResult<()> {
+    host.state().ensure_not_paused()?;
+
+    for Transfer {
+        token_id,
+        amount,
+        from,
+        to,
+        data,
+    } in transfers
+    {
+        let (state, builder) = host.state_and_builder();
+
+        let policy = state
+            .tokens
+            .get(&token_id)
+            .map(|token| token.transfer_policy)
+            .ok_or(ContractError::InvalidTokenId)?;
+
+        let is_operator = state.is_operator(&sender, &from);
+        let authorized = match policy {
+            TransferPolicy::Soulbound => false,
+            TransferPolicy::Free => from == sender || is_operator,
+            TransferPolicy::OperatorOnly => is_operator,
+        };
+        ensure!(authorized, ContractError::Unauthorized);
+
+        let to_address = to.address();
+        state.transfer(&token_id, amount, &from, &to_address, builder)?;
+
+        logger.log(&ContractEvent::Cis2(Cis2Event::Transfer(TransferEvent {
+            token_id,
+            amount,
+            from,
+            to: to_address,
+        })))?;
+
+        if let Receiver::Contract(address, function) = to {
+            let parameter = OnReceivingCis2Params {
+                token_id,
+                amount,
+                from,
+                data,
+            };
+            host.invoke_contract(
+                &address,
+                &parameter,
+                function.as_entrypoint_name(),
+                Amount::zero(),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "updateOperator",
+    parameter = "UpdateOperatorParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+fn contract_update_operator<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let params: UpdateOperatorParams = ctx.parameter_cursor().get()?;
+    update_operators(host, logger, ctx.sender(), params)
+}
+
+fn update_operators<S: HasStateApi>(
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    sender: Address,
+    UpdateOperatorParams(params): UpdateOperatorParams,
+) -> ContractResult<()> {
+    host.state().ensure_not_paused()?;
+
+    let (state, builder) = host.state_and_builder();
+    for param in params {
+        match param.update {
+            OperatorUpdate::Add => state.add_operator(&sender, &param.operator, builder),
+            OperatorUpdate::Remove => state.remove_operator(&sender, &param.operator),
+        }
+
+        logger.log(&ContractEvent::Cis2(Cis2Event::UpdateOperator(
+            UpdateOperatorEvent {
+                owner: sender,
+                operator: param.operator,
+                update: param.update,
+            },
+        )))?;
+    }
+
+    Ok(())
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "operatorOf",
+    parameter = "OperatorOfQueryParams",
+    return_value = "OperatorOfQueryResponse",
+    error = "ContractError"
+)]
+fn contract_operator_of<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<OperatorOfQueryResponse> {
+    let params: OperatorOfQueryParams = ctx.parameter_cursor().get()?;
+    let mut response = Vec::with_capacity(params.queries.len());
+    for query in params.queries {
+        let is_operator = host.state().is_operator(&query.address, &query.owner);
+        response.push(is_operator);
+    }
+    let result = OperatorOfQueryResponse::from(response);
+    Ok(result)
+}
+
+type ContractBalanceOfQueryParams = BalanceOfQueryParams<ContractTokenId>;
+type ContractBalanceOfQueryResponse = BalanceOfQueryResponse<ContractTokenAmount>;
+
+#[receive(
+    contract = "dino_auction",
+    name = "balanceOf",
+    parameter = "ContractBalanceOfQueryParams",
+    return_value = "ContractBalanceOfQueryResponse",
+    error = "ContractError"
+)]
+fn contract_balance_of<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<ContractBalanceOfQueryResponse> {
+    let params: ContractBalanceOfQueryParams = ctx.parameter_cursor().get()?;
+    let mut response = Vec::with_capacity(params.queries.len());
+    for query in params.queries {
+        let amount = host.state().balance(&query.token_id, &query.address)?;
+        response.push(amount);
+    }
+    let result = ContractBalanceOfQueryResponse::from(response);
+    Ok(result)
+}
+
+type ContractTokenMetadataQueryParams = TokenMetadataQueryParams<ContractTokenId>;
+
+#[receive(
+    contract = "dino_auction",
+    name = "tokenMetadata",
+    parameter = "ContractTokenMetadataQueryParams",
+    return_value = "TokenMetadataQueryResponse",
+    error = "ContractError"
+)]
+fn contract_token_metadata<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<TokenMetadataQueryResponse> {
+    let params: ContractTokenMetadataQueryParams = ctx.parameter_cursor().get()?;
+    let mut response = Vec::with_capacity(params.queries.len());
+    for token_id in params.queries {
+        ensure!(
+            host.state().contains_token(&token_id),
+            ContractError::InvalidTokenId
+        );
+
+        let meta = match host.state().tokens.get(&token_id) {
+            Some(url) => url,
+            None => return Err(ContractError::InvalidTokenId),
+        };
+
+        response.push(meta.metadata.to_metadata_url());
+    }
+    let result = TokenMetadataQueryResponse::from(response);
+    Ok(result)
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "supports",
+    parameter = "SupportsQueryParams",
+    return_value = "SupportsQueryResponse",
+    error = "ContractError"
+)]
+fn contract_supports<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<SupportsQueryResponse> {
+    let params: SupportsQueryParams = ctx.parameter_cursor().get()?;
+
+    let mut response = Vec::with_capacity(params.queries.len());
+    for std_id in params.queries {
+        if SUPPORTS_STANDARDS.contains(&std_id.as_standard_identifier()) {
+            response.push(SupportResult::Support);
+        } else {
+            response.push(host.state().have_implementors(&std_id));
+        }
+    }
+    let result = SupportsQueryResponse::from(response);
+    Ok(result)
+}
+
+/// Message signed by the account to permit an action. `payload` is the
+/// serialized parameter of `entry_point`.
+#[derive(Serialize, SchemaType)]
+struct PermitMessage {
+    contract_address: ContractAddress,
+    nonce: u64,
+    timestamp: Timestamp,
+    entry_point: OwnedEntrypointName,
+    #[concordium(size_length = 2)]
+    payload: Vec<u8>,
+}
+
+/// The signature is checked against the keys of the signer account, over the
+/// SHA-256 hash of `GENESIS_HASH`, the address of the contract and `message`.
+#[derive(Serialize, SchemaType)]
+struct PermitParam {
+    signature: AccountSignatures,
+    signer: AccountAddress,
+    message: PermitMessage,
+}
+
+#[derive(Serialize, SchemaType)]
+struct VecOfAccountAddresses {
+    #[concordium(size_length = 2)]
+    queries: Vec<AccountAddress>,
+}
+
+#[derive(Serialize, SchemaType)]
+#[concordium(transparent)]
+struct NonceOfQueryResponse(#[concordium(size_length = 2)] Vec<u64>);
+
+#[derive(Serialize, SchemaType)]
+struct SupportsPermitQueryParams {
+    #[concordium(size_length = 2)]
+    queries: Vec<OwnedEntrypointName>,
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "permit",
+    parameter = "PermitParam",
+    error = "ContractError",
+    crypto_primitives,
+    enable_logger,
+    mutable
+)]
+fn contract_permit<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<()> {
+    let param: PermitParam = ctx.parameter_cursor().get()?;
+
+    let mut signed = GENESIS_HASH.to_vec();
+    signed.extend_from_slice(&to_bytes(&ctx.self_address()));
+    signed.extend_from_slice(&to_bytes(&param.message));
+    let message_hash = crypto_primitives.hash_sha2_256(&signed);
+
+    let verify = host
+        .check_account_signature(param.signer, &param.signature, &message_hash.0)
+        .map_err(|error| match error {
+            CheckAccountSignatureError::MissingAccount => {
+                ContractError::Custom(CustomContractError::PublicKeyNotRegistered)
+            }
+            CheckAccountSignatureError::MalformedData => ContractError::Unauthorized,
+        })?;
+    ensure!(verify, ContractError::Unauthorized);
+
+    execute_permit(ctx, host, logger, crypto_primitives, param.signer, param.message)
+}
+
+/// Run the action of a permit with a checked signature, as `signer`.
+fn execute_permit<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+    signer: AccountAddress,
+    message: PermitMessage,
+) -> ContractResult<()> {
+    ensure!(
+        message.contract_address == ctx.self_address(),
+        ContractError::Unauthorized
+    );
+    ensure!(
+        message.timestamp > ctx.metadata().slot_time(),
+        ContractError::Custom(CustomContractError::PermitExpired)
+    );
+
+    let mut nonce = host.state_mut().nonces.entry(signer).or_insert(0);
+    ensure!(
+        *nonce == message.nonce,
+        ContractError::Custom(CustomContractError::WrongNonce)
+    );
+    *nonce += 1;
+    drop(nonce);
+
+    let sender = Address::Account(signer);
+    let [mint, burn, transfer, update_operator] = SUPPORTS_PERMIT_ENTRYPOINTS;
+    let entry_point = message.entry_point.as_entrypoint_name();
+    if entry_point == mint {
+        let params: MintParams = from_bytes(&message.payload)?;
+        mint_tokens(ctx, host, logger, crypto_primitives, sender, params, Amount::zero())?;
+    } else if entry_point == burn {
+        let params: BurnParams = from_bytes(&message.payload)?;
+        burn_tokens(host, logger, sender, params)?;
+    } else if entry_point == transfer {
+        let params: TransferParameter = from_bytes(&message.payload)?;
+        transfer_tokens(host, logger, sender, params)?;
+    } else if entry_point == update_operator {
+        let params: UpdateOperatorParams = from_bytes(&message.payload)?;
+        update_operators(host, logger, sender, params)?;
+    } else {
+        bail!(ContractError::Custom(CustomContractError::WrongEntrypoint));
+    }
+
+    logger.log(&ContractEvent::Nonce(NonceEvent {
+        account: signer,
+        nonce: message.nonce,
+    }))?;
+
+    Ok(())
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "nonceOf",
+    parameter = "VecOfAccountAddresses",
+    return_value = "NonceOfQueryResponse",
+    error = "ContractError"
+)]
+fn contract_nonce_of<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<NonceOfQueryResponse> {
+    let params: VecOfAccountAddresses = ctx.parameter_cursor().get()?;
+
+    let nonces = params
+        .queries
+        .iter()
+        .map(|account| host.state().nonces.get(account).map_or(0, |nonce| *nonce))
+        .collect();
+
+    Ok(NonceOfQueryResponse(nonces))
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "supportsPermit",
+    parameter = "SupportsPermitQueryParams",
+    return_value = "SupportsQueryResponse",
+    error = "ContractError"
+)]
+fn contract_supports_permit<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    _host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<SupportsQueryResponse> {
+    let params: SupportsPermitQueryParams = ctx.parameter_cursor().get()?;
+
+    let response = params
+        .queries
+        .iter()
+        .map(|entrypoint| {
+            if SUPPORTS_PERMIT_ENTRYPOINTS.contains(&entrypoint.as_entrypoint_name()) {
+                SupportResult::Support
+            } else {
+                SupportResult::NoSupport
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Ok(SupportsQueryResponse::from(response))
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "setImplementors",
+    parameter = "SetImplementorsParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+fn contract_set_implementor<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_role(&ctx.sender(), Role::Admin)?;
+
+    let params: SetImplementorsParams = ctx.parameter_cursor().get()?;
+
+    logger.log(&ContractEvent::ImplementorsSet(ImplementorsSetEvent {
+        id: params.id.as_standard_identifier().to_owned(),
+        implementors: params.implementors.clone(),
+    }))?;
+
+    host.state_mut()
+        .set_implementors(params.id, params.implementors);
+    Ok(())
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+struct AuctionInitParams {
+    tokens: collections::BTreeMap<ContractTokenId, TokenInfo>,
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "init_auction",
+    parameter = "AuctionInitParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+fn contract_init_auction<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_role(&ctx.sender(), Role::AuctionManager)?;
+
+    let params: AuctionInitParams = ctx.parameter_cursor().get()?;
+
+    let (state, state_builder) = host.state_and_builder();
+
+    for (token_id, token_info) in params.tokens {
+        let max_supply = token_info.max_supply;
+        state.create_auction(token_id, token_info, state_builder)?;
+
+        logger.log(&ContractEvent::AuctionCreated(AuctionCreatedEvent {
+            token_id,
+            max_supply,
+        }))?;
+    }
+
+    Ok(())
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+struct CreateAuctionsParams {
+    tokens: Vec<TokenInfo>,
+}
+
+/// Create auctions with ids handed out by the contract, returning the ids in
+/// the order of the tokens in the parameter.
+#[receive(
+    contract = "dino_auction",
+    name = "createAuctions",
+    parameter = "CreateAuctionsParams",
+    return_value = "Vec<ContractTokenId>",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+fn contract_create_auctions<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<Vec<ContractTokenId>> {
+    host.state().ensure_role(&ctx.sender(), Role::AuctionManager)?;
+
+    let params: CreateAuctionsParams = ctx.parameter_cursor().get()?;
+
+    let (state, state_builder) = host.state_and_builder();
+
+    let mut token_ids = Vec::with_capacity(params.tokens.len());
+    for token_info in params.tokens {
+        let token_id = state.next_free_token_id();
+        let max_supply = token_info.max_supply;
+        state.create_auction(token_id, token_info, state_builder)?;
+
+        logger.log(&ContractEvent::AuctionCreated(AuctionCreatedEvent {
+            token_id,
+            max_supply,
+        }))?;
+        token_ids.push(token_id);
+    }
+
+    Ok(token_ids)
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+struct SetCollectionMetadataParams {
+    name: String,
+    metadata: TokenMetadata,
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "setCollectionMetadata",
+    parameter = "SetCollectionMetadataParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+fn contract_set_collection_metadata<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_role(&ctx.sender(), Role::MetadataManager)?;
+
+    let params: SetCollectionMetadataParams = ctx.parameter_cursor().get()?;
+    params.metadata.ensure_valid_hash()?;
+
+    let (state, state_builder) = host.state_and_builder();
+    state
+        .collections
+        .entry(params.name.clone())
+        .or_insert_with(|| Collection::empty(state_builder))
+        .metadata = Some(params.metadata.clone());
+
+    logger.log(&ContractEvent::CollectionMetadataSet(CollectionMetadataSetEvent {
+        name: params.name,
+        metadata: params.metadata,
+    }))?;
+
+    Ok(())
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+struct AuctionWindowParams {
+    token_id: ContractTokenId,
+    start: Option<Timestamp>,
+    end: Option<Timestamp>,
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "updateAuctionWindow",
+    parameter = "AuctionWindowParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+fn contract_update_auction_window<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_role(&ctx.sender(), Role::AuctionManager)?;
+
+    let params: AuctionWindowParams = ctx.parameter_cursor().get()?;
+
+    let mut token = host
+        .state_mut()
+        .tokens
+        .get_mut(&params.token_id)
+        .ok_or(ContractError::Custom(CustomContractError::AuctionNotInitialized))?;
+
+    ensure!(
+        !token.has_ended(ctx.metadata().slot_time()),
+        ContractError::Custom(CustomContractError::AuctionEnded)
+    );
+
+    let mut updated = token.clone();
+    updated.start = params.start;
+    updated.end = params.end;
+    updated.ensure_valid_window()?;
+    updated.ensure_valid_price()?;
+    *token = updated;
+    drop(token);
+
+    logger.log(&ContractEvent::AuctionWindowUpdated(AuctionWindowUpdatedEvent {
+        token_id: params.token_id,
+        start: params.start,
+        end: params.end,
+    }))?;
+
+    Ok(())
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+struct SetMaxSupplyParams {
+    token_id: ContractTokenId,
+    max_supply: ContractTokenAmount,
+}
+
+/// Raise or lower the max supply of a token, at most down to its circulating
+/// supply.
+#[receive(
+    contract = "dino_auction",
+    name = "setMaxSupply",
+    parameter = "SetMaxSupplyParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+fn contract_set_max_supply<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_role(&ctx.sender(), Role::AuctionManager)?;
+
+    let params: SetMaxSupplyParams = ctx.parameter_cursor().get()?;
+    let token_id = params.token_id;
+
+    let state = host.state_mut();
+    ensure!(
+        state.contains_token(&token_id),
+        ContractError::Custom(CustomContractError::AuctionNotInitialized)
+    );
+    ensure!(
+        params.max_supply
+            >= state.get_circulating_supply(&token_id)? + state.reserved_supply(&token_id),
+        ContractError::Custom(CustomContractError::MaxSupplyBelowCirculating)
+    );
+
+    if let Some(mut token) = state.tokens.get_mut(&token_id) {
+        token.max_supply = params.max_supply;
+    }
+
+    logger.log(&ContractEvent::MaxSupplyUpdated(MaxSupplyUpdatedEvent {
+        token_id,
+        max_supply: params.max_supply,
+    }))?;
+
+    Ok(())
+}
+
+/// Close minting of a token for good while its holders keep their balances.
+/// An open bid on the token is returned to the bidder.
+#[receive(
+    contract = "dino_auction",
+    name = "closeMinting",
+    parameter = "AuctionParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+fn contract_close_minting<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_role(&ctx.sender(), Role::AuctionManager)?;
+
+    let params: AuctionParams = ctx.parameter_cursor().get()?;
+    let token_id = params.token_id;
+
+    let state = host.state_mut();
+    ensure!(
+        state.contains_token(&token_id),
+        ContractError::Custom(CustomContractError::AuctionNotInitialized)
+    );
+    ensure!(
+        !state.minting_closed.contains(&token_id),
+        ContractError::Custom(CustomContractError::MintingClosed)
+    );
+
+    state.minting_closed.insert(token_id);
+    state.remove_auction(&token_id);
+
+    logger.log(&ContractEvent::MintingClosed(MintingClosedEvent { token_id }))?;
+
+    Ok(())
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+struct UpdateTokenMetadataParams {
+    token_id: ContractTokenId,
+    metadata: TokenMetadata,
+    /// Freeze the metadata after this update, rejecting any later updates.
+    lock: bool,
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "updateTokenMetadata",
+    parameter = "UpdateTokenMetadataParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+fn contract_update_token_metadata<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_role(&ctx.sender(), Role::MetadataManager)?;
+
+    let params: UpdateTokenMetadataParams = ctx.parameter_cursor().get()?;
+    let token_id = params.token_id;
+    params.metadata.ensure_valid_hash()?;
+
+    let state = host.state_mut();
+    ensure!(
+        !state.locked_metadata.contains(&token_id),
+        ContractError::Custom(CustomContractError::MetadataLocked)
+    );
+
+    let mut token = state
+        .tokens
+        .get_mut(&token_id)
+        .ok_or(ContractError::Custom(CustomContractError::AuctionNotInitialized))?;
+    let metadata_url = params.metadata.to_metadata_url();
+    token.metadata = params.metadata;
+    drop(token);
+
+    logger.log(&ContractEvent::Cis2(Cis2Event::TokenMetadata(
+        TokenMetadataEvent {
+            token_id,
+            metadata_url,
+        },
+    )))?;
+
+    if params.lock {
+        state.locked_metadata.insert(token_id);
+
+        logger.log(&ContractEvent::MetadataLocked(MetadataLockedEvent {
+            token_id,
+        }))?;
+    }
+
+    Ok(())
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+struct ActionBurnParams {
+    tokens: collections::BTreeSet<ContractTokenId>,
+    /// Maximum number of holders to purge in this call. Tokens with holders
+    /// left can be passed again in a later call to continue the purge.
+    limit: u32,
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "burn_auction",
+    parameter = "ActionBurnParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+fn contract_burn_auction<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_role(&ctx.sender(), Role::AuctionManager)?;
+
+    let params: ActionBurnParams = ctx.parameter_cursor().get()?;
+
+    let state = host.state_mut();
+
+    let mut remaining = params.limit;
+    for token_id in params.tokens {
+        ensure!(
+            state.contains_token(&token_id)
+                || state.has_holders(&token_id)
+                || state.has_minters(&token_id),
+            ContractError::Custom(CustomContractError::TokenAlreadyCreated)
+        );
+
+        if let Some(token_info) = state.tokens.remove_and_get(&token_id) {
+            state.remove_from_collection(&token_id, &token_info);
+            state.token_balance.remove(&token_id);
+            state.locked_metadata.remove(&token_id);
+            state.minting_closed.remove(&token_id);
+            state.remove_auction(&token_id);
+            state.lotteries.remove(&token_id);
+
+            logger.log(&ContractEvent::AuctionRemoved(AuctionRemovedEvent {
+                token_id,
+            }))?;
+        }
+        for (owner, amount) in state.purge_holders(&token_id, remaining) {
+            remaining -= 1;
+            logger.log(&ContractEvent::Cis2(Cis2Event::Burn(BurnEvent {
+                token_id,
+                amount,
+                owner,
+            })))?;
+        }
+        remaining -= state.purge_minters(&token_id, remaining);
+    }
+
+    Ok(())
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+struct AirdropEntry {
+    to: Receiver,
+    token_id: ContractTokenId,
+    amount: ContractTokenAmount,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+struct AirdropParams {
+    drops: Vec<AirdropEntry>,
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "airdrop",
+    parameter = "AirdropParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+fn contract_airdrop<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_role(&ctx.sender(), Role::Admin)?;
+    host.state().ensure_not_paused()?;
+
+    let params: AirdropParams = ctx.parameter_cursor().get()?;
+
+    for AirdropEntry {
+        to,
+        token_id,
+        amount,
+    } in params.drops
+    {
+        let (state, builder) = host.state_and_builder();
+
+        let metadata_url = match state.tokens.get(&token_id) {
+            Some(token) => token.metadata.to_metadata_url(),
+            None => bail!(ContractError::Custom(CustomContractError::AuctionNotInitialized)),
+        };
+        state.ensure_supply_left(&token_id, amount)?;
+
+        let to_address = to.address();
+        state.mint(&token_id, amount, &to_address, builder);
+
+        logger.log(&ContractEvent::Cis2(Cis2Event::Mint(MintEvent {
+            token_id,
+            amount,
+            owner: to_address,
+        })))?;
+
+        logger.log(&ContractEvent::Cis2(Cis2Event::TokenMetadata(
+            TokenMetadataEvent {
+                token_id,
+                metadata_url,
+            },
+        )))?;
+
+        if let Receiver::Contract(address, function) = to {
+            let parameter = OnReceivingCis2Params {
+                token_id,
+                amount,
+                from: Address::Contract(ctx.self_address()),
+                data: AdditionalData::empty(),
+            };
+            host.invoke_contract(
+                &address,
+                &parameter,
+                function.as_entrypoint_name(),
+                Amount::zero(),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+struct BidParams {
+    token_id: ContractTokenId,
+    /// Same authorization as needed to mint the token.
+    authorization: MintAuthorization,
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "bid",
+    parameter = "BidParams",
+    error = "ContractError",
+    crypto_primitives,
+    payable,
+    mutable
+)]
+fn contract_bid<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    amount: Amount,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<()> {
+    host.state().ensure_not_paused()?;
+
+    let bidder = match ctx.sender() {
+        Address::Account(a) => a,
+        Address::Contract(_) => bail!(ContractError::Custom(CustomContractError::AccountOnly)),
+    };
+
+    let params: BidParams = ctx.parameter_cursor().get()?;
+    let token_id = params.token_id;
+
+    let state = host.state_mut();
+    authorize_account(ctx, state, crypto_primitives, bidder, &params.authorization)?;
+
+    let token = state
+        .tokens
+        .get(&token_id)
+        .ok_or(ContractError::Custom(CustomContractError::AuctionNotInitialized))?;
+    ensure_token_authorized(
+        crypto_primitives,
+        &bidder,
+        &params.authorization,
+        &token_id,
+        &token,
+    )?;
+    token.ensure_open(ctx.metadata().slot_time())?;
+    ensure!(
+        token.sealed_bid.is_none(),
+        ContractError::Custom(CustomContractError::SealedBidAuction)
+    );
+    drop(token);
+    state.ensure_auction_unit_left(&token_id)?;
+
+    state.place_bid(&token_id, bidder, amount)
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+struct CommitBidParams {
+    token_id: ContractTokenId,
+    commitment: HashSha2256,
+    /// Same authorization as needed to mint the token.
+    authorization: MintAuthorization,
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "commitBid",
+    parameter = "CommitBidParams",
+    error = "ContractError",
+    crypto_primitives,
+    enable_logger,
+    payable,
+    mutable
+)]
+fn contract_commit_bid<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    amount: Amount,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<()> {
+    host.state().ensure_not_paused()?;
+
+    let bidder = match ctx.sender() {
+        Address::Account(a) => a,
+        Address::Contract(_) => bail!(ContractError::Custom(CustomContractError::AccountOnly)),
+    };
+
+    let params: CommitBidParams = ctx.parameter_cursor().get()?;
+    let token_id = params.token_id;
+
+    let (state, builder) = host.state_and_builder();
+    authorize_account(ctx, state, crypto_primitives, bidder, &params.authorization)?;
+
+    let token = state
+        .tokens
+        .get(&token_id)
+        .ok_or(ContractError::Custom(CustomContractError::AuctionNotInitialized))?;
+    ensure_token_authorized(
+        crypto_primitives,
+        &bidder,
+        &params.authorization,
+        &token_id,
+        &token,
+    )?;
+    token.ensure_open(ctx.metadata().slot_time())?;
+    ensure!(
+        amount >= token.sealed_bid()?.min_deposit,
+        ContractError::Custom(CustomContractError::DepositTooLow)
+    );
+    drop(token);
+    state.ensure_supply_left(&token_id, 1.into())?;
+
+    let bid = SealedBid {
+        commitment: params.commitment,
+        deposit: amount,
+    };
+    state.commit_sealed_bid(&token_id, bidder, bid, builder)?;
+
+    logger.log(&ContractEvent::SealedBidCommitted(SealedBidCommittedEvent {
+        token_id,
+        bidder,
+        deposit: amount,
+    }))?;
+
+    Ok(())
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+struct RevealBidParams {
+    token_id: ContractTokenId,
+    amount: Amount,
+    salt: HashSha2256,
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "revealBid",
+    parameter = "RevealBidParams",
+    error = "ContractError",
+    crypto_primitives,
+    enable_logger,
+    mutable
+)]
+fn contract_reveal_bid<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<()> {
+    host.state().ensure_not_paused()?;
+
+    let bidder = match ctx.sender() {
+        Address::Account(a) => a,
+        Address::Contract(_) => bail!(ContractError::Custom(CustomContractError::AccountOnly)),
+    };
+
+    let params: RevealBidParams = ctx.parameter_cursor().get()?;
+    let token_id = params.token_id;
+
+    let state = host.state_mut();
+
+    state
+        .tokens
+        .get(&token_id)
+        .ok_or(ContractError::Custom(CustomContractError::AuctionNotInitialized))?
+        .ensure_reveal_open(ctx.metadata().slot_time())?;
+
+    let bid = state.take_sealed_bid(&token_id, &bidder)?;
+
+    let commitment = crypto_primitives.hash_sha2_256(&to_bytes(&(params.amount, params.salt)));
+    ensure!(
+        commitment == bid.commitment && params.amount <= bid.deposit,
+        ContractError::Custom(CustomContractError::InvalidBidReveal)
+    );
+
+    state.place_revealed_bid(&token_id, bidder, params.amount, bid.deposit)?;
+
+    logger.log(&ContractEvent::SealedBidRevealed(SealedBidRevealedEvent {
+        token_id,
+        bidder,
+        amount: params.amount,
+    }))?;
+
+    Ok(())
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+struct SettleSealedBidParams {
+    token_id: ContractTokenId,
+    bidder: AccountAddress,
+}
+
+/// Settle a sealed bid that was never revealed. Once the reveal phase is over
+/// the forfeited share of the deposit goes to the admin and the rest back to
+/// the bidder, both to be withdrawn with `claim`. Bids on removed auctions
+/// are refunded in full.
+#[receive(
+    contract = "dino_auction",
+    name = "settleSealedBid",
+    parameter = "SettleSealedBidParams",
+    error = "ContractError",
+    mutable
+)]
+fn contract_settle_sealed_bid<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let params: SettleSealedBidParams = ctx.parameter_cursor().get()?;
+    let token_id = params.token_id;
+
+    let state = host.state_mut();
+
+    let forfeit = match state.tokens.get(&token_id) {
+        Some(token) => {
+            let sealed_bid = token.sealed_bid()?.clone();
+            ensure!(
+                ctx.metadata().slot_time() >= sealed_bid.reveal_end,
+                ContractError::Custom(CustomContractError::AuctionNotEnded)
+            );
+            Some(sealed_bid)
+        }
+        None => None,
+    };
+
+    let bid = state.take_sealed_bid(&token_id, &params.bidder)?;
+    let forfeit = forfeit.map_or(Amount::zero(), |sealed_bid| sealed_bid.forfeit(bid.deposit));
+
+    if forfeit > Amount::zero() {
+        let admin = state.admin;
+        state.add_pending_return(admin, forfeit);
+    }
+    if bid.deposit > forfeit {
+        state.add_pending_return(params.bidder, bid.deposit - forfeit);
+    }
+
+    Ok(())
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "close_auction",
+    parameter = "AuctionParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+fn contract_close_auction<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_role(&ctx.sender(), Role::AuctionManager)?;
+    host.state().ensure_not_paused()?;
+
+    let params: AuctionParams = ctx.parameter_cursor().get()?;
+    let token_id = params.token_id;
+
+    let (state, builder) = host.state_and_builder();
+
+    let (metadata_url, payouts) = match state.tokens.get(&token_id) {
+        Some(token) => {
+            if let Some(sealed_bid) = &token.sealed_bid {
+                ensure!(
+                    ctx.metadata().slot_time() >= sealed_bid.reveal_end,
+                    ContractError::Custom(CustomContractError::AuctionNotEnded)
+                );
+            }
+            (token.metadata.to_metadata_url(), token.payouts.clone())
+        }
+        None => bail!(ContractError::Custom(CustomContractError::AuctionNotInitialized)),
+    };
+
+    let (winner, winning_bid) = match state.close_auction(&token_id)? {
+        Some(winner) => winner,
+        None => return Ok(()),
+    };
+
+    // A unit is reserved for the winner while the auction is open, but the
+    // supply can still be gone if minting was closed, in which case the
+    // winning bid is returned instead.
+    if state.ensure_supply_left(&token_id, 1.into()).is_err() {
+        state.add_pending_return(winner, winning_bid);
+        return Ok(());
+    }
+
+    let winner_address = Address::Account(winner);
+    state.mint(&token_id, 1.into(), &winner_address, builder);
+
+    logger.log(&ContractEvent::Cis2(Cis2Event::Mint(MintEvent {
+        token_id,
+        amount: TokenAmountU64::from(1),
+        owner: winner_address,
+    })))?;
+
+    logger.log(&ContractEvent::Cis2(Cis2Event::TokenMetadata(
+        TokenMetadataEvent {
+            token_id,
+            metadata_url,
+        },
+    )))?;
+
+    credit_proceeds(state, logger, token_id, &payouts, winning_bid)?;
+
+    Ok(())
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "claim",
+    error = "ContractError",
+    mutable
+)]
+fn contract_claim<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<()> {
+    let claimant = match ctx.sender() {
+        Address::Account(a) => a,
+        Address::Contract(_) => bail!(ContractError::Custom(CustomContractError::AccountOnly)),
+    };
+
+    let amount = host.state_mut().take_pending_return(&claimant);
+
+    ensure!(
+        amount > Amount::zero(),
+        ContractError::Custom(CustomContractError::NothingToClaim)
+    );
+
+    host.invoke_transfer(&claimant, amount)?;
+
+    Ok(())
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "viewAuction",
+    parameter = "AuctionParams",
+    return_value = "AuctionState",
+    error = "ContractError"
+)]
+fn contract_view_auction<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<AuctionState> {
+    let params: AuctionParams = ctx.parameter_cursor().get()?;
+    ensure!(
+        host.state().contains_token(&params.token_id),
+        ContractError::InvalidTokenId
+    );
+
+    let auction = host
+        .state()
+        .auctions
+        .get(&params.token_id)
+        .map_or_else(AuctionState::default, |a| a.clone());
+    Ok(auction)
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "viewStatementId",
+    parameter = "AuctionParams",
+    return_value = "Option<String>",
+    error = "ContractError"
+)]
+fn contract_view_statement_id<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<Option<String>> {
+    let params: AuctionParams = ctx.parameter_cursor().get()?;
+
+    let statement_id = host
+        .state()
+        .tokens
+        .get(&params.token_id)
+        .ok_or(ContractError::InvalidTokenId)?
+        .statement_id
+        .clone();
+    Ok(statement_id)
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "currentPrice",
+    parameter = "AuctionParams",
+    return_value = "Amount",
+    error = "ContractError"
+)]
+fn contract_current_price<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<Amount> {
+    let params: AuctionParams = ctx.parameter_cursor().get()?;
+
+    let price = host
+        .state()
+        .tokens
+        .get(&params.token_id)
+        .ok_or(ContractError::InvalidTokenId)?
+        .current_price(ctx.metadata().slot_time());
+    Ok(price)
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "withdrawProceeds",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+fn contract_withdraw_proceeds<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let recipient = match ctx.sender() {
+        Address::Account(a) => a,
+        Address::Contract(_) => bail!(ContractError::Custom(CustomContractError::AccountOnly)),
+    };
+
+    let amount = host
+        .state_mut()
+        .proceeds
+        .remove_and_get(&recipient)
+        .unwrap_or_else(Amount::zero);
+    ensure!(
+        amount > Amount::zero(),
+        ContractError::Custom(CustomContractError::NothingToWithdraw)
+    );
+
+    host.invoke_transfer(&recipient, amount)?;
+
+    logger.log(&ContractEvent::ProceedsWithdrawn(ProceedsWithdrawnEvent {
+        recipient,
+        amount,
+    }))?;
+
+    Ok(())
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+struct CommitLotteryParams {
+    token_id: ContractTokenId,
+    /// SHA256 hash of the seed revealed after the auction window has ended.
+    seed_hash: HashSha2256,
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "commitLottery",
+    parameter = "CommitLotteryParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+fn contract_commit_lottery<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_role(&ctx.sender(), Role::AuctionManager)?;
+
+    let params: CommitLotteryParams = ctx.parameter_cursor().get()?;
+    let token_id = params.token_id;
+
+    let (state, builder) = host.state_and_builder();
+
+    let token = state
+        .tokens
+        .get(&token_id)
+        .ok_or(ContractError::Custom(CustomContractError::AuctionNotInitialized))?;
+    ensure!(
+        token.end.is_some(),
+        ContractError::Custom(CustomContractError::InvalidAuctionWindow)
+    );
+    token.ensure_open(ctx.metadata().slot_time())?;
+    drop(token);
+
+    ensure!(
+        state.lotteries.get(&token_id).is_none(),
+        ContractError::Custom(CustomContractError::LotteryAlreadyCommitted)
+    );
+    state
+        .lotteries
+        .insert(token_id, Lottery::empty(params.seed_hash, builder));
+
+    logger.log(&ContractEvent::LotteryCommitted(LotteryCommittedEvent {
+        token_id,
+        seed_hash: params.seed_hash,
+    }))?;
+
+    Ok(())
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+struct EnterLotteryParams {
+    token_id: ContractTokenId,
+    /// Same authorization as needed to mint the token, so each entry is
+    /// backed by a verified identity.
+    authorization: MintAuthorization,
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "enterLottery",
+    parameter = "EnterLotteryParams",
+    error = "ContractError",
+    crypto_primitives,
+    enable_logger,
+    mutable
+)]
+fn contract_enter_lottery<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<()> {
+    host.state().ensure_not_paused()?;
+
+    let account = match ctx.sender() {
+        Address::Account(a) => a,
+        Address::Contract(_) => bail!(ContractError::Custom(CustomContractError::AccountOnly)),
+    };
+
+    let params: EnterLotteryParams = ctx.parameter_cursor().get()?;
+    let token_id = params.token_id;
+
+    let state = host.state_mut();
+    authorize_account(ctx, state, crypto_primitives, account, &params.authorization)?;
+
+    let token = state
+        .tokens
+        .get(&token_id)
+        .ok_or(ContractError::Custom(CustomContractError::AuctionNotInitialized))?;
+    ensure_token_authorized(
+        crypto_primitives,
+        &account,
+        &params.authorization,
+        &token_id,
+        &token,
+    )?;
+    token.ensure_open(ctx.metadata().slot_time())?;
+    drop(token);
+
+    let index = state
+        .lotteries
+        .get_mut(&token_id)
+        .ok_or(ContractError::Custom(CustomContractError::LotteryNotCommitted))?
+        .enter(account)?;
+
+    logger.log(&ContractEvent::LotteryEntered(LotteryEnteredEvent {
+        token_id,
+        account,
+        index,
+    }))?;
+
+    Ok(())
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+struct RevealLotteryParams {
+    token_id: ContractTokenId,
+    seed: HashSha2256,
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "revealLottery",
+    parameter = "RevealLotteryParams",
+    error = "ContractError",
+    crypto_primitives,
+    enable_logger,
+    mutable
+)]
+fn contract_reveal_lottery<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> ContractResult<()> {
+    host.state().ensure_role(&ctx.sender(), Role::AuctionManager)?;
+
+    let params: RevealLotteryParams = ctx.parameter_cursor().get()?;
+    let token_id = params.token_id;
+
+    let state = host.state_mut();
+
+    let ended = state
+        .tokens
+        .get(&token_id)
+        .ok_or(ContractError::Custom(CustomContractError::AuctionNotInitialized))?
+        .has_ended(ctx.metadata().slot_time());
+    ensure!(
+        ended,
+        ContractError::Custom(CustomContractError::AuctionNotEnded)
+    );
+
+    let supply_left = state
+        .get_token_supply(&token_id)?
+        .0
+        .saturating_sub(state.get_circulating_supply(&token_id)?.0);
+
+    let winners = state
+        .lotteries
+        .get_mut(&token_id)
+        .ok_or(ContractError::Custom(CustomContractError::LotteryNotCommitted))?
+        .reveal(
+            crypto_primitives,
+            params.seed,
+            supply_left.min(u64::from(u32::MAX)) as u32,
+        )?;
+
+    logger.log(&ContractEvent::LotteryRevealed(LotteryRevealedEvent {
+        token_id,
+        seed: params.seed,
+        winners,
+    }))?;
+
+    Ok(())
+}
+
+#[derive(Serialize, SchemaType, Debug, PartialEq, Eq)]
+struct ViewLottery {
+    seed_hash: HashSha2256,
+    seed: Option<HashSha2256>,
+    entrants: u32,
+    winners: u32,
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "viewLottery",
+    parameter = "AuctionParams",
+    return_value = "ViewLottery",
+    error = "ContractError"
+)]
+fn contract_view_lottery<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<ViewLottery> {
+    let params: AuctionParams = ctx.parameter_cursor().get()?;
+
+    let lottery = host
+        .state()
+        .lotteries
+        .get(&params.token_id)
+        .ok_or(ContractError::Custom(CustomContractError::LotteryNotCommitted))?;
+
+    Ok(ViewLottery {
+        seed_hash: lottery.seed_hash,
+        seed: lottery.seed,
+        entrants: lottery.entrant_count,
+        winners: lottery.winners,
+    })
+}
+
+#[derive(Serialize, SchemaType)]
+struct UpgradeParams {
+    module: ModuleReference,
+}
+
+/// Upgrade the contract to a new module and invoke `migrate` of the new module
+/// right after, which brings the state to its layout and leaves a state of
+/// that layout unchanged. The upgrade is rolled back if the migration fails.
+/// The entrypoint is low level as the state can change layout during the
+/// migration, so it must not be written back with the layout of this module
+/// afterwards.
+#[receive(
+    contract = "dino_auction",
+    name = "upgrade",
+    parameter = "UpgradeParams",
+    error = "ContractError",
+    low_level
+)]
+fn contract_upgrade<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<S, StateApiType = S>,
+) -> ContractResult<()> {
+    let state: State<S> = host.state().read_root()?;
+    state.ensure_role(&ctx.sender(), Role::Admin)?;
+
+    let params: UpgradeParams = ctx.parameter_cursor().get()?;
+
+    host.upgrade(params.module)?;
+
+    host.invoke_contract_raw(
+        &ctx.self_address(),
+        Parameter::empty(),
+        EntrypointName::new_unchecked("migrate"),
+        Amount::zero(),
+    )?;
+
+    Ok(())
+}
+
+/// Convert the state from the layout of an earlier version to the one of
+/// `STATE_VERSION`. Only the contract itself can call it, from `upgrade`.
+/// Modules changing the layout bump `STATE_VERSION` and add a step here that
+/// reads the state of the previous version and writes it in the new layout.
+#[receive(
+    contract = "dino_auction",
+    name = "migrate",
+    error = "ContractError",
+    low_level
+)]
+fn contract_migrate<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<S, StateApiType = S>,
+) -> ContractResult<()> {
+    ensure!(
+        ctx.sender() == Address::Contract(ctx.self_address()),
+        ContractError::Unauthorized
+    );
+
+    migrate_state(host.state_mut())
+}
+
+/// Run the migration steps from the version of the state up to
+/// `STATE_VERSION`, one version at a time.
+fn migrate_state<S: HasStateApi>(state_api: &mut S) -> ContractResult<()> {
+    let mut version: u32 = state_api.read_root()?;
+    ensure!(
+        version <= STATE_VERSION,
+        ContractError::Custom(CustomContractError::UnknownStateVersion)
+    );
+    while version < STATE_VERSION {
+        match version {
+            1 => migrate_from_v1(state_api)?,
+            2 => migrate_from_v2(state_api)?,
+            3 => migrate_from_v3(state_api)?,
+            4 => migrate_from_v4(state_api)?,
+            5 => migrate_from_v5(state_api)?,
+            6 => migrate_from_v6(state_api)?,
+            _ => bail!(ContractError::Custom(CustomContractError::UnknownStateVersion)),
+        }
+        version += 1;
+    }
+    Ok(())
+}
+
+/// Set the version of the state to `version` and append `fields` to its root.
+/// The fields added by each version go at the end of `State`, so the root of
+/// the previous version is extended in place without reading the rest of it.
+fn append_to_root<S: HasStateApi>(
+    state_api: &mut S,
+    version: u32,
+    fields: &[u8],
+) -> ContractResult<()> {
+    let mut root = state_api.lookup_entry(&[]).unwrap_abort();
+    let mut bytes = vec![0u8; root.size().unwrap_abort() as usize];
+    root.read_exact(&mut bytes)?;
+    bytes[..4].copy_from_slice(&to_bytes(&version));
+    bytes.extend_from_slice(fields);
+
+    root.move_to_start();
+    root.write_all(&bytes).unwrap_abort();
+    Ok(())
+}
+
+/// `CollectionCap` as stored before version 6, with a cap on every token.
+#[derive(Serial, Deserial, Clone)]
+struct CollectionCapV1 {
+    name: String,
+    max_tokens_per_account: u32,
+}
+
+/// Set the version of the state to `version` and remove the bytes in `range`
+/// from its root, for a field dropped from `State`.
+fn remove_from_root<S: HasStateApi>(
+    state_api: &mut S,
+    version: u32,
+    range: core::ops::Range<usize>,
+) -> ContractResult<()> {
+    let mut root = state_api.lookup_entry(&[]).unwrap_abort();
+    let mut bytes = vec![0u8; root.size().unwrap_abort() as usize];
+    root.read_exact(&mut bytes)?;
+    bytes[..4].copy_from_slice(&to_bytes(&version));
+    bytes.drain(range);
+
+    root.move_to_start();
+    root.write_all(&bytes).unwrap_abort();
+    root.truncate(bytes.len() as u32).unwrap_abort();
+    Ok(())
+}
+
+/// `TokenInfo` as stored by version 1 of the state, before `statement_id`.
+#[derive(Serial, Deserial, Clone)]
+struct TokenInfoV1 {
+    metadata: TokenMetadata,
+    max_supply: ContractTokenAmount,
+    max_per_account: ContractTokenAmount,
+    transfer_policy: TransferPolicy,
+    start: Option<Timestamp>,
+    end: Option<Timestamp>,
+    collection: Option<CollectionCapV1>,
+    merkle_root: Option<HashSha2256>,
+    sealed_bid: Option<SealedBidConfig>,
+    price: Option<DutchPrice>,
+    payouts: Vec<Payout>,
+}
+
+/// `TokenInfo` as stored by versions 2 to 5 of the state.
+#[derive(Serial, Deserial, Clone)]
+struct TokenInfoV2 {
+    metadata: TokenMetadata,
+    max_supply: ContractTokenAmount,
+    max_per_account: ContractTokenAmount,
+    transfer_policy: TransferPolicy,
+    start: Option<Timestamp>,
+    end: Option<Timestamp>,
+    collection: Option<CollectionCapV1>,
+    merkle_root: Option<HashSha2256>,
+    sealed_bid: Option<SealedBidConfig>,
+    price: Option<DutchPrice>,
+    payouts: Vec<Payout>,
+    statement_id: Option<String>,
+}
+
+impl From<TokenInfoV1> for TokenInfoV2 {
+    fn from(token: TokenInfoV1) -> Self {
+        TokenInfoV2 {
+            metadata: token.metadata,
+            max_supply: token.max_supply,
+            max_per_account: token.max_per_account,
+            transfer_policy: token.transfer_policy,
+            start: token.start,
+            end: token.end,
+            collection: token.collection,
+            merkle_root: token.merkle_root,
+            sealed_bid: token.sealed_bid,
+            price: token.price,
+            payouts: token.payouts,
+            statement_id: None,
+        }
+    }
+}
+
+impl From<TokenInfoV2> for TokenInfo {
+    fn from(token: TokenInfoV2) -> Self {
+        TokenInfo {
+            metadata: token.metadata,
+            max_supply: token.max_supply,
+            max_per_account: token.max_per_account,
+            transfer_policy: token.transfer_policy,
+            start: token.start,
+            end: token.end,
+            collection: token.collection.map(|cap| CollectionCap {
+                name: cap.name,
+                max_tokens_per_account: Some(cap.max_tokens_per_account),
+            }),
+            merkle_root: token.merkle_root,
+            sealed_bid: token.sealed_bid,
+            price: token.price,
+            payouts: token.payouts,
+            statement_id: token.statement_id,
+        }
+    }
+}
+
+/// Leading fields of the state root up to `tokens`, for reading the tokens of
+/// a state that can not be read as a whole with the layout of this module.
+#[derive(DeserialWithState)]
+#[concordium(state_parameter = "S")]
+struct RootTokens<S> {
+    _state_version: u32,
+    _state: StateMap<Address, AddressState<S>, S>,
+    tokens: StateMap<ContractTokenId, TokenInfo, S>,
+}
+
+/// `RootTokens` with the tokens of version 1 of the state.
+#[derive(DeserialWithState)]
+#[concordium(state_parameter = "S")]
+struct RootTokensV1<S> {
+    _state_version: u32,
+    _state: StateMap<Address, AddressState<S>, S>,
+    tokens: StateMap<ContractTokenId, TokenInfoV1, S>,
+}
+
+/// `RootTokens` with the tokens of versions 2 to 5 of the state.
+#[derive(DeserialWithState)]
+#[concordium(state_parameter = "S")]
+struct RootTokensV2<S> {
+    _state_version: u32,
+    _state: StateMap<Address, AddressState<S>, S>,
+    tokens: StateMap<ContractTokenId, TokenInfoV2, S>,
+}
+
+/// Version 2 adds `statement_id` to the tokens, rewriting each token with
+/// no statement required.
+fn migrate_from_v1<S: HasStateApi>(state_api: &mut S) -> ContractResult<()> {
+    let mut old: RootTokensV1<S> = state_api.read_root()?;
+    let tokens: Vec<(ContractTokenId, TokenInfoV1)> = old
+        .tokens
+        .iter()
+        .map(|(token_id, token)| (*token_id, token.clone()))
+        .collect();
+
+    // Inserting over an entry reads the old value with the new layout, so
+    // each token is removed with the old layout first.
+    let mut new: RootTokensV2<S> = state_api.read_root()?;
+    for (token_id, token) in tokens {
+        old.tokens.remove(&token_id);
+        new.tokens.insert(token_id, token.into());
+    }
+    append_to_root(state_api, 2, &[])
+}
+
+/// Version 3 adds `next_token_id` and `collections`.
+fn migrate_from_v2<S: HasStateApi>(state_api: &mut S) -> ContractResult<()> {
+    let mut state_builder = StateBuilder::open(state_api.clone());
+    let collections: StateMap<String, Collection<S>, S> = state_builder.new_map();
+    append_to_root(state_api, 3, &to_bytes(&(0u32, collections)))
+}
+
+/// Version 4 adds `minting_closed`.
+fn migrate_from_v3<S: HasStateApi>(state_api: &mut S) -> ContractResult<()> {
+    let mut state_builder = StateBuilder::open(state_api.clone());
+    let minting_closed: StateSet<ContractTokenId, S> = state_builder.new_set();
+    append_to_root(state_api, 4, &to_bytes(&minting_closed))
+}
+
+/// Leading fields of the state root up to `minted`, for indexing the mints
+/// recorded before version 5.
+#[derive(DeserialWithState)]
+#[concordium(state_parameter = "S")]
+struct RootMinted<S> {
+    _state_version: u32,
+    _state: StateMap<Address, AddressState<S>, S>,
+    tokens: StateMap<ContractTokenId, TokenInfoV2, S>,
+    _token_balance: StateMap<ContractTokenId, ContractTokenAmount, S>,
+    _holders: StateMap<ContractTokenId, StateSet<Address, S>, S>,
+    minted: StateMap<(AccountAddress, ContractTokenId), ContractTokenAmount, S>,
+}
+
+/// Version 5 adds `minters`, indexed from the mints already recorded.
+fn migrate_from_v4<S: HasStateApi>(state_api: &mut S) -> ContractResult<()> {
+    let root: RootMinted<S> = state_api.read_root()?;
+    let recorded: Vec<(AccountAddress, ContractTokenId)> = root
+        .minted
+        .iter()
+        .filter(|(_, amount)| **amount > 0.into())
+        .map(|(key, _)| *key)
+        .collect();
+
+    let mut state_builder = StateBuilder::open(state_api.clone());
+    let mut minters: StateMap<ContractTokenId, Minters<S>, S> = state_builder.new_map();
+    for (account, token_id) in recorded {
+        let collection = root
+            .tokens
+            .get(&token_id)
+            .and_then(|token| token.collection.as_ref().map(|cap| cap.name.clone()));
+        minters
+            .entry(token_id)
+            .or_insert_with(|| Minters {
+                collection,
+                accounts: state_builder.new_set(),
+            })
+            .accounts
+            .insert(account);
+    }
+    append_to_root(state_api, 5, &to_bytes(&minters))
+}
+
+/// `Collection` as stored before version 6, without a cap of its own.
+#[derive(Serial, DeserialWithState, Deletable)]
+#[concordium(state_parameter = "S")]
+struct CollectionV3<S> {
+    metadata: Option<TokenMetadata>,
+    tokens: StateSet<ContractTokenId, S>,
+}
+
+/// Leading fields of the state root of versions 5 and 6 up to `collections`,
+/// generic over the layout of the collections.
+#[derive(DeserialWithState)]
+#[concordium(state_parameter = "S")]
+struct RootCollections<S, C> {
+    _state_version: u32,
+    _state: StateMap<Address, AddressState<S>, S>,
+    _tokens: StateMap<ContractTokenId, TokenInfo, S>,
+    _token_balance: StateMap<ContractTokenId, ContractTokenAmount, S>,
+    _holders: StateMap<ContractTokenId, StateSet<Address, S>, S>,
+    _minted: StateMap<(AccountAddress, ContractTokenId), ContractTokenAmount, S>,
+    _collection_mints: StateMap<(AccountAddress, String), u32, S>,
+    _public_keys: StateMap<AccountAddress, PublicKeyEd25519, S>,
+    _nonces: StateMap<AccountAddress, u64, S>,
+    _locked_metadata: StateSet<ContractTokenId, S>,
+    _implementors: StateMap<StandardIdentifierOwned, Vec<ContractAddress>, S>,
+    _verify_keys: StateSet<PublicKeyEd25519, S>,
+    _admin: AccountAddress,
+    _proposed_admin: Option<AccountAddress>,
+    _roles: StateSet<(Address, Role), S>,
+    _paused: bool,
+    _auctions: StateMap<ContractTokenId, AuctionState, S>,
+    _lotteries: StateMap<ContractTokenId, Lottery<S>, S>,
+    _sealed_bids: StateMap<ContractTokenId, StateMap<AccountAddress, SealedBid, S>, S>,
+    _pending_returns: StateMap<AccountAddress, Amount, S>,
+    _used_nonces: StateSet<u64, S>,
+    _proceeds: StateMap<AccountAddress, Amount, S>,
+    _next_token_id: u32,
+    collections: StateMap<String, C, S>,
+}
+
+/// Version 6 keeps the collection cap once per collection instead of on each
+/// token. Tokens of a collection created with different caps leave it with
+/// the lowest of them.
+fn migrate_from_v5<S: HasStateApi>(state_api: &mut S) -> ContractResult<()> {
+    let mut old_tokens: RootTokensV2<S> = state_api.read_root()?;
+    let tokens: Vec<(ContractTokenId, TokenInfoV2)> = old_tokens
+        .tokens
+        .iter()
+        .map(|(token_id, token)| (*token_id, token.clone()))
+        .collect();
+
+    let mut caps: collections::BTreeMap<String, u32> = collections::BTreeMap::new();
+    let mut new_tokens: RootTokens<S> = state_api.read_root()?;
+    for (token_id, token) in tokens {
+        if let Some(cap) = &token.collection {
+            let max = caps.entry(cap.name.clone()).or_insert(cap.max_tokens_per_account);
+            *max = (*max).min(cap.max_tokens_per_account);
+        }
+        old_tokens.tokens.remove(&token_id);
+        new_tokens.tokens.insert(token_id, token.into());
+    }
+
+    let mut old: RootCollections<S, CollectionV3<S>> = state_api.read_root()?;
+    let names: Vec<String> = old.collections.iter().map(|(name, _)| name.clone()).collect();
+    let mut new: RootCollections<S, Collection<S>> = state_api.read_root()?;
+    for name in names {
+        // The tokens of the collection are kept, so its entry is taken out
+        // without deleting them.
+        if let Some(collection) = old.collections.remove_and_get(&name) {
+            let collection = Collection {
+                metadata: collection.metadata,
+                max_tokens_per_account: caps.get(&name).copied(),
+                tokens: collection.tokens,
+            };
+            new.collections.insert(name, collection);
+        }
+    }
+    append_to_root(state_api, 6, &[])
+}
+
+/// Leading fields of the state root up to `public_keys`, the keys permits
+/// were checked against before version 7.
+#[derive(Serial, DeserialWithState)]
+#[concordium(state_parameter = "S")]
+struct RootPublicKeys<S> {
+    _state_version: u32,
+    _state: StateMap<Address, AddressState<S>, S>,
+    _tokens: StateMap<ContractTokenId, TokenInfo, S>,
+    _token_balance: StateMap<ContractTokenId, ContractTokenAmount, S>,
+    _holders: StateMap<ContractTokenId, StateSet<Address, S>, S>,
+    _minted: StateMap<(AccountAddress, ContractTokenId), ContractTokenAmount, S>,
+    _collection_mints: StateMap<(AccountAddress, String), u32, S>,
+    public_keys: StateMap<AccountAddress, PublicKeyEd25519, S>,
+}
+
+/// Version 7 drops `public_keys`, as permits are checked against the keys of
+/// the signer account.
+fn migrate_from_v6<S: HasStateApi>(state_api: &mut S) -> ContractResult<()> {
+    let mut root: RootPublicKeys<S> = state_api.read_root()?;
+    let end = to_bytes(&root).len();
+    let start = end - to_bytes(&root.public_keys).len();
+    root.public_keys.clear();
+    remove_from_root(state_api, 7, start..end)
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+struct VerifyKeyParams {
+    key: PublicKeyEd25519,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+struct RotateVerifyKeyParams {
+    old: PublicKeyEd25519,
+    new: PublicKeyEd25519,
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "addVerifyKey",
+    parameter = "VerifyKeyParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+fn contract_add_verify_key<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_role(&ctx.sender(), Role::Admin)?;
+
+    let params: VerifyKeyParams = ctx.parameter_cursor().get()?;
+    host.state_mut().add_verify_key(params.key)?;
+
+    logger.log(&ContractEvent::VerifyKeyChanged(VerifyKeyChangedEvent {
+        key: params.key,
+        update: VerifyKeyUpdate::Added,
+    }))?;
+
+    Ok(())
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "revokeVerifyKey",
+    parameter = "VerifyKeyParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+fn contract_revoke_verify_key<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_role(&ctx.sender(), Role::Admin)?;
+
+    let params: VerifyKeyParams = ctx.parameter_cursor().get()?;
+    host.state_mut().revoke_verify_key(&params.key)?;
+
+    logger.log(&ContractEvent::VerifyKeyChanged(VerifyKeyChangedEvent {
+        key: params.key,
+        update: VerifyKeyUpdate::Revoked,
+    }))?;
+
+    Ok(())
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "rotateVerifyKey",
+    parameter = "RotateVerifyKeyParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+fn contract_rotate_verify_key<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_role(&ctx.sender(), Role::Admin)?;
+
+    let params: RotateVerifyKeyParams = ctx.parameter_cursor().get()?;
+    let state = host.state_mut();
+    state.revoke_verify_key(&params.old)?;
+    state.add_verify_key(params.new)?;
+
+    logger.log(&ContractEvent::VerifyKeyChanged(VerifyKeyChangedEvent {
+        key: params.old,
+        update: VerifyKeyUpdate::Revoked,
+    }))?;
+    logger.log(&ContractEvent::VerifyKeyChanged(VerifyKeyChangedEvent {
+        key: params.new,
+        update: VerifyKeyUpdate::Added,
+    }))?;
+
+    Ok(())
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "viewVerifyKeys",
+    return_value = "Vec<PublicKeyEd25519>"
+)]
+fn contract_view_verify_keys<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<PublicKeyEd25519>> {
+    Ok(host.state().verify_keys.iter().map(|key| *key).collect())
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+struct ProposeAdminParams {
+    admin: AccountAddress,
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+struct RoleParams {
+    address: Address,
+    role: Role,
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "proposeAdmin",
+    parameter = "ProposeAdminParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+fn contract_propose_admin<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_role(&ctx.sender(), Role::Admin)?;
+
+    let params: ProposeAdminParams = ctx.parameter_cursor().get()?;
+    host.state_mut().proposed_admin = Some(params.admin);
+
+    logger.log(&ContractEvent::AdminProposed(AdminProposedEvent {
+        proposed: params.admin,
+    }))?;
+
+    Ok(())
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "acceptAdmin",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+fn contract_accept_admin<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    let state = host.state_mut();
+
+    let proposed = match state.proposed_admin {
+        Some(proposed) if ctx.sender().matches_account(&proposed) => proposed,
+        _ => bail!(ContractError::Unauthorized),
+    };
+
+    let previous = state.admin;
+    state.admin = proposed;
+    state.proposed_admin = None;
+
+    logger.log(&ContractEvent::AdminChanged(AdminChangedEvent {
+        previous,
+        admin: proposed,
+    }))?;
+
+    Ok(())
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "grantRole",
+    parameter = "RoleParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+fn contract_grant_role<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_role(&ctx.sender(), Role::Admin)?;
+
+    let params: RoleParams = ctx.parameter_cursor().get()?;
+    host.state_mut().grant_role(params.address, params.role)?;
+
+    logger.log(&ContractEvent::RoleChanged(RoleChangedEvent {
+        address: params.address,
+        role: params.role,
+        update: RoleUpdate::Granted,
+    }))?;
+
+    Ok(())
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "revokeRole",
+    parameter = "RoleParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+fn contract_revoke_role<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_role(&ctx.sender(), Role::Admin)?;
+
+    let params: RoleParams = ctx.parameter_cursor().get()?;
+    host.state_mut().revoke_role(params.address, params.role)?;
+
+    logger.log(&ContractEvent::RoleChanged(RoleChangedEvent {
+        address: params.address,
+        role: params.role,
+        update: RoleUpdate::Revoked,
+    }))?;
+
+    Ok(())
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "hasRole",
+    parameter = "RoleParams",
+    return_value = "bool",
+    error = "ContractError"
+)]
+fn contract_has_role<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ContractResult<bool> {
+    let params: RoleParams = ctx.parameter_cursor().get()?;
+    Ok(host.state().has_role(&params.address, params.role))
+}
+
+#[derive(Serial, Deserial, SchemaType)]
+struct SetPausedParams {
+    paused: bool,
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "setPaused",
+    parameter = "SetPausedParams",
+    error = "ContractError",
+    enable_logger,
+    mutable
+)]
+fn contract_set_paused<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> ContractResult<()> {
+    host.state().ensure_role(&ctx.sender(), Role::Admin)?;
+
+    let params: SetPausedParams = ctx.parameter_cursor().get()?;
+    host.state_mut().paused = params.paused;
+
+    logger.log(&ContractEvent::PausedChanged(PausedChangedEvent {
+        paused: params.paused,
+    }))?;
+
+    Ok(())
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "get_admin",
+    return_value = "AccountAddress"
+)]
+fn contract_get_admin<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<AccountAddress> {
+    Ok(host.state().admin)
+}
+
+#[receive(
+    contract = "dino_auction",
+    name = "get_owner",
+    return_value = "AccountAddress"
+)]
+fn contract_get_owner<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    _host: &impl HasHost<State<S>, StateApiType = S>
+) -> ContractResult<AccountAddress> {
+    Ok(ctx.owner())
+}
+
+// Tests
+
+#[concordium_cfg_test]
+#[allow(deprecated)]
+mod tests {
+    use super::*;
+    use sha2::{Digest, Sha256};
+    use test_infrastructure::*;
+    #[cfg(feature = "crypto-primitives")]
+    use ed25519_dalek::{ExpandedSecretKey, PublicKey, SecretKey};
+    #[cfg(feature = "crypto-primitives")]
+    use rand::rngs::OsRng;
+
+    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
+    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
+    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
+    const ADDRESS_1: Address = Address::Account(ACCOUNT_1);
+    const TOKEN_0: ContractTokenId = TokenIdU32(0);
+    const TOKEN_1: ContractTokenId = TokenIdU32(42);
+    const CONTRACT_0: ContractAddress = ContractAddress {
+        index: 1,
+        subindex: 0,
+    };
+
+    fn get_token_metadata() -> TokenMetadata {
+        let mut hasher = Sha256::new();
+        hasher.update(b"hello world");
+        let hash = hasher.finalize();
+        let hex_hash = hex::encode(hash);
+
+        TokenMetadata {
+            url: String::from("hello"),
+            hash: hex_hash,
+        }
+    }
+
+    fn get_token_info(metadata: TokenMetadata, max_supply: u64) -> TokenInfo {
+        TokenInfo {
+            metadata,
+            max_supply: max_supply.into(),
+            max_per_account: max_supply.into(),
+            transfer_policy: TransferPolicy::Soulbound,
+            start: None,
+            end: None,
+            collection: None,
+            merkle_root: None,
+            sealed_bid: None,
+            price: None,
+            payouts: Vec::new(),
+            statement_id: None,
+        }
+    }
+
+    fn get_token_info_v1(metadata: TokenMetadata, max_supply: u64) -> TokenInfoV1 {
+        TokenInfoV1 {
+            metadata,
+            max_supply: max_supply.into(),
+            max_per_account: max_supply.into(),
+            transfer_policy: TransferPolicy::Soulbound,
+            start: None,
+            end: None,
+            collection: None,
+            merkle_root: None,
+            sealed_bid: None,
+            price: None,
+            payouts: Vec::new(),
+        }
+    }
+
+    fn initial_state<S: HasStateApi>(state_builder: &mut StateBuilder<S>) -> State<S> {
+        let mut state = State::empty(state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+
+        let meta = get_token_metadata();
+
+        state.tokens.insert(TOKEN_0, get_token_info(meta.clone(), 400));
+        state.tokens.insert(TOKEN_1, get_token_info(meta.clone(), 1));
+
+        state.mint(&TOKEN_0, 1.into(), &ADDRESS_0, state_builder);
+        state.mint(&TOKEN_1, 1.into(), &ADDRESS_0, state_builder);
+        state
+    }
+
+    fn get_voucher(tokens: &collections::BTreeSet<ContractTokenId>) -> MintVoucher {
+        MintVoucher {
+            contract: CONTRACT_0,
+            account: ACCOUNT_0,
+            statement_id: String::from("eu-nationality"),
+            tokens: tokens.clone(),
+            nonce: 0,
+            expiry: Timestamp::from_timestamp_millis(100),
+        }
+    }
+
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn get_bid_params(account: AccountAddress, token_id: ContractTokenId) -> BidParams {
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(token_id);
+        BidParams {
+            token_id,
+            authorization: MintAuthorization::Voucher {
+                voucher: MintVoucher {
+                    account,
+                    ..get_voucher(&tokens)
+                },
+                signature: SignatureEd25519([0u8; 64]),
+            },
+        }
+    }
+
+    fn get_mint_amounts(
+        tokens: &collections::BTreeSet<ContractTokenId>,
+    ) -> collections::BTreeMap<ContractTokenId, ContractTokenAmount> {
+        tokens.iter().map(|token_id| (*token_id, 1.into())).collect()
+    }
+
+    fn set_mint_context(ctx: &mut TestReceiveContext) {
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_self_address(CONTRACT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+    }
+
+    #[cfg(feature = "crypto-primitives")]
+    fn create_crypto_primitives(message: &[u8]) -> (SignatureEd25519, PublicKeyEd25519) {
+        let mut csprng = OsRng {};
+        let secret_key: SecretKey = SecretKey::generate(&mut csprng);
+        let public_key: PublicKey = (&secret_key).into();
+        let expanded: ExpandedSecretKey = ExpandedSecretKey::from(&secret_key);
+        let signed = expanded.sign(message, &public_key);
+        (
+            SignatureEd25519(signed.to_bytes()),
+            PublicKeyEd25519(public_key.to_bytes()),
+        )
+    }
+
+    #[concordium_test]
+    fn test_init() {
+        // Arrange
+        let mut ctx = TestInitContext::empty();
+        ctx.set_init_origin(ACCOUNT_0);
+        let mut builder = TestStateBuilder::new();
+        let parameter = InitParams {
+            verify_key: PublicKeyEd25519([0u8; 32]),
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        // Act
+        let result = contract_init(&ctx, &mut builder);
+
+        // Assert
+        let state = result.expect_report("Contract initialization failed");
+        claim_eq!(
+            state.tokens.iter().count(),
+            0,
+            "Only one token is initialized"
+        );
+    }
+
+    #[concordium_test]
+    fn given_sender_is_admin_when_burn_auction_then_ok() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(TOKEN_0);
+        let params = ActionBurnParams { tokens, limit: 10 };
+        let parameter_bytes = to_bytes(&params);
+        ctx.set_parameter(&parameter_bytes);
+
+        // Act
+        let result: ContractResult<()> = contract_burn_auction(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim!(result.is_ok());
+        claim!(host.state().tokens.get(&TOKEN_0).is_none());
+
+        let address_state = host.state().state.get(&ADDRESS_0).expect("Address missing");
+        claim!(address_state.balances.get(&TOKEN_0).is_none());
+        claim!(address_state.balances.get(&TOKEN_1).is_some());
+
+        claim!(host.state().token_balance.get(&TOKEN_0).is_none());
+        claim!(host.state().token_balance.get(&TOKEN_1).is_some());
+    }
+
+    #[concordium_test]
+    fn given_sender_without_auction_role_when_burn_auction_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_owner(ACCOUNT_0);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(TOKEN_0);
+        let params = ActionBurnParams { tokens, limit: 10 };
+        let parameter_bytes = to_bytes(&params);
+        ctx.set_parameter(&parameter_bytes);
+
+        // Act
+        let result: ContractResult<()> = contract_burn_auction(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim!(result.is_err());
+        claim_eq!(result.unwrap_err(), ContractError::Unauthorized);
+    }
+
+    #[concordium_test]
+    fn given_sender_is_admin_when_init_auction_then_ok() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+
+        let token_info = get_token_metadata();
+
+        let mut tokens = collections::BTreeMap::new();
+        tokens.insert(TOKEN_0, get_token_info(token_info.clone(), 400));
+        tokens.insert(TOKEN_1, get_token_info(token_info.clone(), 1));
+        let parameter = AuctionInitParams { tokens };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_init_auction(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim!(result.is_ok());
+
+        let max_0 = host
+            .state()
+            .tokens
+            .get(&TOKEN_0)
+            .expect_report("Token not inserted");
+
+        claim_eq!(max_0.max_supply, 400.into());
+    }
+
+    #[concordium_test]
+    fn given_sender_without_auction_role_when_init_auction_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_owner(ACCOUNT_0);
+
+        let token_info = get_token_metadata();
+
+        let mut tokens = collections::BTreeMap::new();
+        tokens.insert(TOKEN_0, get_token_info(token_info.clone(), 400));
+        let parameter = AuctionInitParams { tokens };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_init_auction(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim!(result.is_err());
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_token_not_existing_when_mint_then_return_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        set_mint_context(&mut ctx);
+
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(TOKEN_0);
+        let parameter = MintParams {
+            tokens: get_mint_amounts(&tokens),
+            authorization: MintAuthorization::Voucher {
+                voucher: get_voucher(&tokens),
+                signature: SignatureEd25519([0u8; 64]),
+            },
+            to: None,
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> = contract_mint(&ctx, &mut host, Amount::zero(), &mut logger, &crypto);
+
+        // Assert
+        claim!(result.is_err());
+        claim_eq!(
+            result.unwrap_err(),
+            ContractError::Custom(CustomContractError::AuctionNotInitialized)
+        );
+    }
+
+    #[concordium_test]
+    #[cfg(feature = "crypto-primitives")]
+    fn given_crypto_primitives_when_signature_not_correct_then_return_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        set_mint_context(&mut ctx);
+
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(TOKEN_0);
+
+        let voucher = get_voucher(&tokens);
+        let (signature, _) = create_crypto_primitives(&to_bytes(&voucher));
+
+        let parameter = MintParams {
+            tokens: get_mint_amounts(&tokens),
+            authorization: MintAuthorization::Voucher { voucher, signature },
+            to: None,
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+
+        // Act
+        let result: ContractResult<()> = contract_mint(&ctx, &mut host, Amount::zero(), &mut logger, &crypto);
+
+        // Assert
+        claim!(result.is_err());
+        claim_eq!(
+            result.unwrap_err(),
+            ContractError::Unauthorized
+        );
+    }
+
+
+    #[concordium_test]
+    #[cfg(feature = "crypto-primitives")]
+    fn given_crypto_primitives_when_mint_then_add_token() {
+        // Arrange
+        let token_info = get_token_metadata();
+
+        let mut ctx = TestReceiveContext::empty();
+        set_mint_context(&mut ctx);
+
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(TOKEN_0);
+
+        let voucher = get_voucher(&tokens);
+        let (signature, verify_key) = create_crypto_primitives(&to_bytes(&voucher));
+
+        let parameter = MintParams {
+            tokens: get_mint_amounts(&tokens),
+            authorization: MintAuthorization::Voucher { voucher, signature },
+            to: None,
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        
+        let mut state = State::empty(&mut state_builder, ACCOUNT_0, verify_key);
+        state.tokens.insert(TOKEN_0, get_token_info(token_info.clone(), 1));
+
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+
+        // Act
+        let result: ContractResult<()> = contract_mint(&ctx, &mut host, Amount::zero(), &mut logger, &crypto);
+
+        // Assert
+        claim!(result.is_ok());
+    }
+
+    #[concordium_test]
+    #[cfg(feature = "crypto-primitives")]
+    fn given_crypto_primitives_when_mint_with_empty_metadata_then_return_without_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        set_mint_context(&mut ctx);
+
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(TOKEN_0);
+
+        let voucher = get_voucher(&tokens);
+        let (signature, verify_key) = create_crypto_primitives(&to_bytes(&voucher));
+
+        let parameter = MintParams {
+            tokens: get_mint_amounts(&tokens),
+            authorization: MintAuthorization::Voucher { voucher, signature },
+            to: None,
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        
+        let mut state = State::empty(&mut state_builder, ACCOUNT_0, verify_key);
+        state.tokens.insert(TOKEN_0, get_token_info(TokenMetadata{hash:"".to_string(), url: "".to_string()}, 1));
+
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+
+        // Act
+        let result: ContractResult<()> = contract_mint(&ctx, &mut host, Amount::zero(), &mut logger, &crypto);
+
+        // Assert
+        claim!(result.is_ok());
+    }
+
+    #[concordium_test]
+    fn when_burn_then_ok() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+
+        let params = BurnParams{
+            token_id: TOKEN_0,
+            amount: 1.into(),
+        };
+        let parameter_bytes = to_bytes(&params);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_burn(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim!(result.is_ok());
+        
+        claim_eq!(*host.state().token_balance.get(&TOKEN_0).expect_report("Token balance not present"), 0.into());
+        claim_eq!(*host.state().token_balance.get(&TOKEN_1).expect_report("Token balance not present"), 1.into());
+
+        let balance0 = host
+            .state()
+            .balance(&TOKEN_0, &ADDRESS_0)
+            .expect_report("Token is expected to exist");
+        claim_eq!(
+            balance0,
+            0.into(),
+            "Initial tokens are owned by the contract instantiater"
+        );
+
+        let balance1 = host
+            .state()
+            .balance(&TOKEN_1, &ADDRESS_0)
+            .expect_report("Token is expected to exist");
+        claim_eq!(
+            balance1,
+            1.into(),
+            "Initial tokens are owned by the contract instantiater"
+        );
+    }
+
+    #[concordium_test]
+    fn given_token_not_exist_when_burn_then_error() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+
+        let params = BurnParams{
+            token_id: TOKEN_0,
+            amount: 1.into(),
+        };
+
+        let parameter_bytes = to_bytes(&params);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_burn(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim_eq!(result.expect_err("Should be error"), ContractError::Custom(CustomContractError::AuctionNotInitialized));
+    }
+
+    #[concordium_test]
+    fn given_sender_not_have_token_when_burn_then_error() {
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+
+        let params = BurnParams{
+            token_id: TOKEN_0,
+            amount: 1.into(),
+        };
+
+        let parameter_bytes = to_bytes(&params);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        state.tokens.insert(TOKEN_0, get_token_info(get_token_metadata(), 1));
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_burn(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim_eq!(result.expect_err("Should be error"), ContractError::Custom(CustomContractError::NoBalanceToBurn));
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn when_mint_then_add_token() {
+        // Arrange
+        let token_info = get_token_metadata();
+
+        let mut ctx = TestReceiveContext::empty();
+        set_mint_context(&mut ctx);
+
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(TOKEN_0);
+        tokens.insert(TOKEN_1);
+        let parameter = MintParams {
+            tokens: get_mint_amounts(&tokens),
+            authorization: MintAuthorization::Voucher {
+                voucher: get_voucher(&tokens),
+                signature: SignatureEd25519([0u8; 64]),
+            },
+            to: None,
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        
+        let mut state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        state.tokens.insert(TOKEN_0, get_token_info(token_info.clone(), 1));
+        state.tokens.insert(TOKEN_1, get_token_info(token_info.clone(), 1));
+
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> = contract_mint(&ctx, &mut host, Amount::zero(), &mut logger, &crypto);
+
+        // Assert
+        claim!(result.is_ok());
+        claim_eq!(*host.state().token_balance.get(&TOKEN_0).expect("token not present in balance"), 1.into());
+        let balance0 = host
+            .state()
+            .balance(&TOKEN_0, &ADDRESS_0)
+            .expect_report("Token is expected to exist");
+        claim_eq!(
+            balance0,
+            1.into(),
+            "Initial tokens are owned by the contract instantiater"
+        );
+
+        let balance1 = host
+            .state()
+            .balance(&TOKEN_1, &ADDRESS_0)
+            .expect_report("Token is expected to exist");
+        claim_eq!(
+            balance1,
+            1.into(),
+            "Initial tokens are owned by the contract instantiater"
+        );
+
+        claim_eq!(logger.logs.len(), 4, "Exactly four events should be logged");
+        claim!(
+            logger.logs.contains(&to_bytes(&Cis2Event::Mint(MintEvent {
+                owner: ADDRESS_0,
+                token_id: TOKEN_0,
+                amount: ContractTokenAmount::from(1),
+            }))),
+            "Expected an event for minting TOKEN_0"
+        );
+        claim!(
+            logger.logs.contains(&to_bytes(&Cis2Event::Mint(MintEvent {
+                owner: ADDRESS_0,
+                token_id: TOKEN_1,
+                amount: ContractTokenAmount::from(1),
+            }))),
+            "Expected an event for minting TOKEN_1"
+        );
+        claim!(
+            logger.logs.contains(&to_bytes(
+                &Cis2Event::TokenMetadata::<_, ContractTokenAmount>(TokenMetadataEvent {
+                    token_id: TOKEN_0,
+                    metadata_url: token_info.to_metadata_url(),
+                })
+            )),
+            "Expected an event for token metadata for TOKEN_0"
+        );
+        claim!(
+            logger.logs.contains(&to_bytes(
+                &Cis2Event::TokenMetadata::<_, ContractTokenAmount>(TokenMetadataEvent {
+                    token_id: TOKEN_1,
+                    metadata_url: token_info.to_metadata_url(),
+                })
+            )),
+            "Expected an event for token metadata for TOKEN_1"
+        );
+    }
+
+    #[concordium_test]
+    fn test_transfer_account() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+
+        let transfer = Transfer {
+            token_id: TOKEN_0,
+            amount: ContractTokenAmount::from(100),
+            from: ADDRESS_0,
+            to: Receiver::from_account(ACCOUNT_1),
+            data: AdditionalData::empty(),
+        };
+        let parameter = TransferParams::from(vec![transfer]);
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_transfer(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim!(result.is_err());
+        claim_eq!(result.unwrap_err(), ContractError::Unauthorized);
+    }
+
+    #[concordium_test]
+    fn test_operator_transfer() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+
+        let transfer = Transfer {
+            from: ADDRESS_0,
+            to: Receiver::from_account(ACCOUNT_1),
+            token_id: TOKEN_0,
+            amount: ContractTokenAmount::from(100),
+            data: AdditionalData::empty(),
+        };
+        let parameter = TransferParams::from(vec![transfer]);
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state.add_operator(&ADDRESS_0, &ADDRESS_1, &mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_transfer(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim!(result.is_err());
+        claim_eq!(result.unwrap_err(), ContractError::Unauthorized);
+    }
+
+    #[concordium_test]
+    fn test_add_operator() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+
+        let update = UpdateOperator {
+            operator: ADDRESS_1,
+            update: OperatorUpdate::Add,
+        };
+        let parameter = UpdateOperatorParams(vec![update]);
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_update_operator(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim!(result.is_ok(), "Results in rejection");
+
+        let is_operator = host.state().is_operator(&ADDRESS_1, &ADDRESS_0);
+        claim!(is_operator, "Account should be an operator");
+
+        let operator_of_query = OperatorOfQuery {
+            address: ADDRESS_1,
+            owner: ADDRESS_0,
+        };
+
+        let operator_of_query_vector = OperatorOfQueryParams {
+            queries: vec![operator_of_query],
+        };
+        let parameter_bytes = to_bytes(&operator_of_query_vector);
+
+        ctx.set_parameter(&parameter_bytes);
+
+        let result: ContractResult<OperatorOfQueryResponse> = contract_operator_of(&ctx, &host);
+
+        claim_eq!(
+            result.expect_report("Failed getting result value").0,
+            [true],
+            "Account should be an operator in the query response"
+        );
+
+        claim_eq!(logger.logs.len(), 1, "One event should be logged");
+        claim_eq!(
+            logger.logs[0],
+            to_bytes(
+                &Cis2Event::<ContractTokenId, ContractTokenAmount>::UpdateOperator(
+                    UpdateOperatorEvent {
+                        owner: ADDRESS_0,
+                        operator: ADDRESS_1,
+                        update: OperatorUpdate::Add,
+                    }
+                )
+            ),
+            "Incorrect event emitted"
+        )
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_higher_bid_when_bid_then_previous_bidder_can_claim() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_self_address(CONTRACT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+
+        let parameter_bytes = to_bytes(&get_bid_params(ACCOUNT_1, TOKEN_0));
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state
+            .place_bid(&TOKEN_0, ACCOUNT_0, Amount::from_ccd(10))
+            .expect_report("Initial bid failed");
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> = contract_bid(&ctx, &mut host, Amount::from_ccd(20), &crypto);
+
+        // Assert
+        claim!(result.is_ok());
+
+        let auction = host.state().auctions.get(&TOKEN_0).expect_report("Auction missing");
+        claim_eq!(auction.highest_bidder, Some(ACCOUNT_1));
+        claim_eq!(auction.highest_bid, Amount::from_ccd(20));
+        claim_eq!(
+            *host.state().pending_returns.get(&ACCOUNT_0).expect_report("No pending return"),
+            Amount::from_ccd(10)
+        );
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_lower_bid_when_bid_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_self_address(CONTRACT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+
+        let parameter_bytes = to_bytes(&get_bid_params(ACCOUNT_1, TOKEN_0));
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state
+            .place_bid(&TOKEN_0, ACCOUNT_0, Amount::from_ccd(10))
+            .expect_report("Initial bid failed");
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> = contract_bid(&ctx, &mut host, Amount::from_ccd(10), &crypto);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::BidTooLow)
+        );
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_bids_when_close_auction_then_mint_to_highest_bidder() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+        ctx.set_owner(ACCOUNT_0);
+
+        let parameter_bytes = to_bytes(&AuctionParams { token_id: TOKEN_0 });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state
+            .place_bid(&TOKEN_0, ACCOUNT_1, Amount::from_ccd(20))
+            .expect_report("Bid failed");
+        let mut host = TestHost::new(state, state_builder);
+        host.set_self_balance(Amount::from_ccd(20));
+
+        // Act
+        let result: ContractResult<()> = contract_close_auction(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim!(result.is_ok());
+
+        let balance = host
+            .state()
+            .balance(&TOKEN_0, &ADDRESS_1)
+            .expect_report("Token is expected to exist");
+        claim_eq!(balance, 1.into());
+        claim!(host.state().auctions.get(&TOKEN_0).expect_report("Auction missing").closed);
+        claim_eq!(
+            *host.state().proceeds.get(&ACCOUNT_0).expect_report("No proceeds"),
+            Amount::from_ccd(20)
+        );
+        claim!(
+            logger.logs.contains(&to_bytes(&Cis2Event::Mint(MintEvent {
+                owner: ADDRESS_1,
+                token_id: TOKEN_0,
+                amount: ContractTokenAmount::from(1),
+            }))),
+            "Expected an event for minting TOKEN_0"
+        );
+
+        let parameter_bytes = to_bytes(&get_bid_params(ACCOUNT_0, TOKEN_0));
+        ctx.set_parameter(&parameter_bytes);
+        ctx.set_self_address(CONTRACT_0);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+        let result: ContractResult<()> =
+            contract_bid(&ctx, &mut host, Amount::from_ccd(30), &crypto);
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::AuctionClosed)
+        );
+    }
+
+    #[concordium_test]
+    fn given_supply_gone_when_close_auction_then_winner_refunded() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+
+        let parameter_bytes = to_bytes(&AuctionParams { token_id: TOKEN_1 });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state
+            .place_bid(&TOKEN_1, ACCOUNT_1, Amount::from_ccd(20))
+            .expect_report("Bid failed");
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_close_auction(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim!(result.is_ok());
+        claim!(host.state().auctions.get(&TOKEN_1).expect_report("Auction missing").closed);
+        claim_eq!(
+            host.state().balance(&TOKEN_1, &ADDRESS_1).expect_report("Token missing"),
+            0.into()
+        );
+        claim_eq!(
+            *host.state().pending_returns.get(&ACCOUNT_1).expect_report("No pending return"),
+            Amount::from_ccd(20)
+        );
+        claim!(logger.logs.is_empty());
+    }
+
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn get_voucher_mint_params(token_id: ContractTokenId) -> MintParams {
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(token_id);
+        MintParams {
+            tokens: get_mint_amounts(&tokens),
+            authorization: MintAuthorization::Voucher {
+                voucher: get_voucher(&tokens),
+                signature: SignatureEd25519([0u8; 64]),
+            },
+            to: None,
+        }
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_open_auction_when_mint_then_minted_from_unreserved_supply() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        set_mint_context(&mut ctx);
+
+        let parameter_bytes = to_bytes(&get_voucher_mint_params(TOKEN_0));
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state
+            .place_bid(&TOKEN_0, ACCOUNT_1, Amount::from_ccd(20))
+            .expect_report("Bid failed");
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> = contract_mint(&ctx, &mut host, Amount::zero(), &mut logger, &crypto);
+
+        // Assert
+        claim!(result.is_ok());
+        claim_eq!(
+            host.state().get_circulating_supply(&TOKEN_0).expect_report("Token missing"),
+            2.into()
+        );
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_only_reserved_unit_left_when_mint_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        set_mint_context(&mut ctx);
+
+        let parameter_bytes = to_bytes(&get_voucher_mint_params(TOKEN_0));
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        if let Some(mut token) = state.tokens.get_mut(&TOKEN_0) {
+            token.max_supply = 2.into();
+        }
+        state
+            .place_bid(&TOKEN_0, ACCOUNT_1, Amount::from_ccd(20))
+            .expect_report("Bid failed");
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> = contract_mint(&ctx, &mut host, Amount::zero(), &mut logger, &crypto);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::MaxSupplyReached)
+        );
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_highest_bid_on_last_unit_when_bid_then_outbid() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_self_address(CONTRACT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+
+        let parameter_bytes = to_bytes(&get_bid_params(ACCOUNT_1, TOKEN_0));
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        if let Some(mut token) = state.tokens.get_mut(&TOKEN_0) {
+            token.max_supply = 2.into();
+        }
+        state
+            .place_bid(&TOKEN_0, ACCOUNT_0, Amount::from_ccd(20))
+            .expect_report("Bid failed");
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> =
+            contract_bid(&ctx, &mut host, Amount::from_ccd(30), &crypto);
+
+        // Assert
+        claim!(result.is_ok());
+        let auction = host.state().auctions.get(&TOKEN_0).expect_report("Auction missing");
+        claim_eq!(auction.highest_bidder, Some(ACCOUNT_1));
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_voucher_of_other_account_when_bid_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_self_address(CONTRACT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+
+        let parameter_bytes = to_bytes(&get_bid_params(ACCOUNT_0, TOKEN_0));
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> = contract_bid(&ctx, &mut host, Amount::from_ccd(20), &crypto);
+
+        // Assert
+        claim_eq!(result.expect_err("Should be error"), ContractError::Unauthorized);
+        claim!(host.state().auctions.get(&TOKEN_0).is_none());
+    }
+
+    #[concordium_test]
+    fn given_pending_return_when_claim_then_transfer() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state
+            .place_bid(&TOKEN_0, ACCOUNT_0, Amount::from_ccd(10))
+            .expect_report("Bid failed");
+        state
+            .place_bid(&TOKEN_0, ACCOUNT_1, Amount::from_ccd(20))
+            .expect_report("Bid failed");
+        let mut host = TestHost::new(state, state_builder);
+        host.set_self_balance(Amount::from_ccd(30));
+
+        // Act
+        let result: ContractResult<()> = contract_claim(&ctx, &mut host);
+
+        // Assert
+        claim!(result.is_ok());
+        claim!(host.transfer_occurred(&ACCOUNT_0, Amount::from_ccd(10)));
+
+        let result: ContractResult<()> = contract_claim(&ctx, &mut host);
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::NothingToClaim)
+        );
+    }
+
+    #[concordium_test]
+    fn given_open_bid_when_burn_auction_then_bid_returned() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state
+            .place_bid(&TOKEN_0, ACCOUNT_1, Amount::from_ccd(20))
+            .expect_report("Bid failed");
+        let mut host = TestHost::new(state, state_builder);
+
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(TOKEN_0);
+        let parameter_bytes = to_bytes(&ActionBurnParams { tokens, limit: 10 });
+        ctx.set_parameter(&parameter_bytes);
+
+        // Act
+        let result: ContractResult<()> = contract_burn_auction(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim!(result.is_ok());
+        claim!(host.state().auctions.get(&TOKEN_0).is_none());
+        claim_eq!(
+            *host.state().pending_returns.get(&ACCOUNT_1).expect_report("No pending return"),
+            Amount::from_ccd(20)
+        );
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_expired_voucher_when_mint_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        set_mint_context(&mut ctx);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(100));
+
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(TOKEN_0);
+        let parameter = MintParams {
+            tokens: get_mint_amounts(&tokens),
+            authorization: MintAuthorization::Voucher {
+                voucher: get_voucher(&tokens),
+                signature: SignatureEd25519([0u8; 64]),
+            },
+            to: None,
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> = contract_mint(&ctx, &mut host, Amount::zero(), &mut logger, &crypto);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::VoucherExpired)
+        );
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_used_voucher_when_mint_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        set_mint_context(&mut ctx);
+
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(TOKEN_0);
+        let parameter = MintParams {
+            tokens: get_mint_amounts(&tokens),
+            authorization: MintAuthorization::Voucher {
+                voucher: get_voucher(&tokens),
+                signature: SignatureEd25519([0u8; 64]),
+            },
+            to: None,
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        state.tokens.insert(TOKEN_0, get_token_info(get_token_metadata(), 2));
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        let result: ContractResult<()> = contract_mint(&ctx, &mut host, Amount::zero(), &mut logger, &crypto);
+        claim!(result.is_ok());
+
+        // Act
+        let result: ContractResult<()> = contract_mint(&ctx, &mut host, Amount::zero(), &mut logger, &crypto);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::VoucherAlreadyUsed)
+        );
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_token_not_in_voucher_when_mint_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        set_mint_context(&mut ctx);
+
+        let mut voucher_tokens = collections::BTreeSet::new();
+        voucher_tokens.insert(TOKEN_0);
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(TOKEN_1);
+        let parameter = MintParams {
+            tokens: get_mint_amounts(&tokens),
+            authorization: MintAuthorization::Voucher {
+                voucher: get_voucher(&voucher_tokens),
+                signature: SignatureEd25519([0u8; 64]),
+            },
+            to: None,
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> = contract_mint(&ctx, &mut host, Amount::zero(), &mut logger, &crypto);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::TokenNotInVoucher)
+        );
+    }
+
+    #[cfg(feature = "crypto-primitives")]
+    fn get_allowlist_root(
+        crypto: &TestCryptoPrimitives,
+        accounts: [AccountAddress; 2],
+    ) -> (HashSha2256, [HashSha2256; 2]) {
+        let leaves = accounts.map(|account| crypto.hash_sha2_256(&account.0));
+        let (left, right) = if leaves[0].0 <= leaves[1].0 {
+            (leaves[0], leaves[1])
+        } else {
+            (leaves[1], leaves[0])
+        };
+        let root = crypto.hash_sha2_256(&[left.0, right.0].concat());
+        (root, leaves)
+    }
+
+    #[concordium_test]
+    #[cfg(feature = "crypto-primitives")]
+    fn given_account_in_allowlist_when_mint_then_minted() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        set_mint_context(&mut ctx);
+
+        let crypto = TestCryptoPrimitives::new();
+        let (root, leaves) = get_allowlist_root(&crypto, [ACCOUNT_0, ACCOUNT_1]);
+
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(TOKEN_0);
+        let parameter = MintParams {
+            tokens: get_mint_amounts(&tokens),
+            authorization: MintAuthorization::Allowlist {
+                proof: vec![leaves[1]],
+            },
+            to: None,
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        let mut token_info = get_token_info(get_token_metadata(), 1);
+        token_info.merkle_root = Some(root);
+        state.tokens.insert(TOKEN_0, token_info);
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_mint(&ctx, &mut host, Amount::zero(), &mut logger, &crypto);
+
+        // Assert
+        claim!(result.is_ok());
+        claim_eq!(
+            host.state().balance(&TOKEN_0, &ADDRESS_0).expect_report("Should have balance"),
+            ContractTokenAmount::from(1)
+        );
+    }
+
+    #[concordium_test]
+    #[cfg(feature = "crypto-primitives")]
+    fn given_account_not_in_allowlist_when_mint_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        set_mint_context(&mut ctx);
+
+        let crypto = TestCryptoPrimitives::new();
+        let (root, leaves) = get_allowlist_root(&crypto, [ACCOUNT_1, AccountAddress([2u8; 32])]);
+
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(TOKEN_0);
+        let parameter = MintParams {
+            tokens: get_mint_amounts(&tokens),
+            authorization: MintAuthorization::Allowlist {
+                proof: vec![leaves[1]],
+            },
+            to: None,
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        let mut token_info = get_token_info(get_token_metadata(), 1);
+        token_info.merkle_root = Some(root);
+        state.tokens.insert(TOKEN_0, token_info);
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_mint(&ctx, &mut host, Amount::zero(), &mut logger, &crypto);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::NotInAllowlist)
+        );
+    }
+
+    fn get_lottery_state<S: HasStateApi>(
+        state_builder: &mut StateBuilder<S>,
+        max_supply: u64,
+        seed_hash: HashSha2256,
+        entrants: &[AccountAddress],
+    ) -> State<S> {
+        let mut state = State::empty(state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        state.tokens.insert(
+            TOKEN_0,
+            TokenInfo {
+                end: Some(Timestamp::from_timestamp_millis(10)),
+                ..get_token_info(get_token_metadata(), max_supply)
+            },
+        );
+        let mut lottery = Lottery::empty(seed_hash, state_builder);
+        for entrant in entrants {
+            lottery.enter(*entrant).expect_report("Should enter");
+        }
+        state.lotteries.insert(TOKEN_0, lottery);
+        state
+    }
+
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn get_enter_lottery_params(account: AccountAddress) -> EnterLotteryParams {
+        let BidParams {
+            token_id,
+            authorization,
+        } = get_bid_params(account, TOKEN_0);
+        EnterLotteryParams {
+            token_id,
+            authorization,
+        }
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_committed_lottery_when_enter_lottery_then_entered() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_self_address(CONTRACT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(5));
+        let parameter_bytes = to_bytes(&get_enter_lottery_params(ACCOUNT_1));
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = get_lottery_state(&mut state_builder, 1, HashSha2256([0u8; 32]), &[ACCOUNT_0]);
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> =
+            contract_enter_lottery(&ctx, &mut host, &mut logger, &crypto);
+
+        // Assert
+        claim!(result.is_ok());
+        claim_eq!(
+            logger.logs,
+            [to_bytes(&ContractEvent::LotteryEntered(LotteryEnteredEvent {
+                token_id: TOKEN_0,
+                account: ACCOUNT_1,
+                index: 1,
+            }))]
+        );
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_entered_when_enter_lottery_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_self_address(CONTRACT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(5));
+        let parameter_bytes = to_bytes(&get_enter_lottery_params(ACCOUNT_0));
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = get_lottery_state(&mut state_builder, 1, HashSha2256([0u8; 32]), &[ACCOUNT_0]);
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> =
+            contract_enter_lottery(&ctx, &mut host, &mut logger, &crypto);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::LotteryAlreadyEntered)
+        );
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_voucher_of_other_account_when_enter_lottery_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_self_address(CONTRACT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(5));
+        let parameter_bytes = to_bytes(&get_enter_lottery_params(ACCOUNT_0));
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = get_lottery_state(&mut state_builder, 1, HashSha2256([0u8; 32]), &[]);
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> =
+            contract_enter_lottery(&ctx, &mut host, &mut logger, &crypto);
+
+        // Assert
+        claim_eq!(result.expect_err("Should be error"), ContractError::Unauthorized);
+        claim!(logger.logs.is_empty());
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_lottery_not_revealed_when_mint_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        set_mint_context(&mut ctx);
+
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(TOKEN_0);
+        let parameter = MintParams {
+            tokens: get_mint_amounts(&tokens),
+            authorization: MintAuthorization::Voucher {
+                voucher: get_voucher(&tokens),
+                signature: SignatureEd25519([0u8; 64]),
+            },
+            to: None,
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = get_lottery_state(&mut state_builder, 1, HashSha2256([0u8; 32]), &[ACCOUNT_0]);
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> = contract_mint(&ctx, &mut host, Amount::zero(), &mut logger, &crypto);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::LotteryNotRevealed)
+        );
+    }
+
+    #[concordium_test]
+    #[cfg(feature = "crypto-primitives")]
+    fn given_wrong_seed_when_reveal_lottery_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let parameter_bytes = to_bytes(&RevealLotteryParams {
+            token_id: TOKEN_0,
+            seed: HashSha2256([2u8; 32]),
+        });
+        ctx.set_parameter(&parameter_bytes);
+
+        let crypto = TestCryptoPrimitives::new();
+        let seed_hash = crypto.hash_sha2_256(&[1u8; 32]);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = get_lottery_state(&mut state_builder, 1, seed_hash, &[ACCOUNT_0]);
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> =
+            contract_reveal_lottery(&ctx, &mut host, &mut logger, &crypto);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::InvalidLotterySeed)
+        );
+    }
+
+    #[concordium_test]
+    #[cfg(feature = "crypto-primitives")]
+    fn given_more_entrants_than_supply_when_reveal_lottery_then_winners_can_claim() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+        let seed = HashSha2256([1u8; 32]);
+        let parameter_bytes = to_bytes(&RevealLotteryParams {
+            token_id: TOKEN_0,
+            seed,
+        });
+        ctx.set_parameter(&parameter_bytes);
+
+        let crypto = TestCryptoPrimitives::new();
+        let entrants = [ACCOUNT_0, ACCOUNT_1, AccountAddress([2u8; 32])];
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = get_lottery_state(
+            &mut state_builder,
+            2,
+            crypto.hash_sha2_256(&seed.0),
+            &entrants,
+        );
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> =
+            contract_reveal_lottery(&ctx, &mut host, &mut logger, &crypto);
+
+        // Assert
+        claim!(result.is_ok());
+        claim_eq!(
+            logger.logs,
+            [to_bytes(&ContractEvent::LotteryRevealed(LotteryRevealedEvent {
+                token_id: TOKEN_0,
+                seed,
+                winners: 2,
+            }))]
+        );
+
+        let mut lottery = host
+            .state_mut()
+            .lotteries
+            .get_mut(&TOKEN_0)
+            .expect_report("Lottery missing");
+        let claimed = entrants
+            .iter()
+            .filter(|entrant| lottery.claim(entrant, 1.into()).is_ok())
+            .count();
+        claim_eq!(claimed, 2);
+    }
+
+    #[concordium_test]
+    fn given_lottery_winner_when_claim_more_than_one_then_error() {
+        // Arrange
+        let mut state_builder = TestStateBuilder::new();
+        let mut lottery = Lottery::empty(HashSha2256([0u8; 32]), &mut state_builder);
+        lottery.enter(ACCOUNT_0).expect_report("Should enter");
+        lottery.seed = Some(HashSha2256([1u8; 32]));
+        lottery.winners = 1;
+
+        // Act
+        let result = lottery.claim(&ACCOUNT_0, 2.into());
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::InvalidLotteryClaim)
+        );
+        claim!(lottery.claim(&ACCOUNT_0, 1.into()).is_ok());
+    }
+
+    fn get_sealed_bid_state<S: HasStateApi>(state_builder: &mut StateBuilder<S>) -> State<S> {
+        let mut state = State::empty(state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        state.tokens.insert(
+            TOKEN_0,
+            TokenInfo {
+                end: Some(Timestamp::from_timestamp_millis(10)),
+                sealed_bid: Some(SealedBidConfig {
+                    reveal_end: Timestamp::from_timestamp_millis(20),
+                    min_deposit: Amount::from_ccd(10),
+                    forfeit_bps: 1_000,
+                }),
+                ..get_token_info(get_token_metadata(), 1)
+            },
+        );
+        state
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_sealed_bid_auction_when_bid_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_self_address(CONTRACT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+        let parameter_bytes = to_bytes(&get_bid_params(ACCOUNT_1, TOKEN_0));
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut state_builder = TestStateBuilder::new();
+        let state = get_sealed_bid_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> = contract_bid(&ctx, &mut host, Amount::from_ccd(20), &crypto);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::SealedBidAuction)
+        );
+    }
+
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn get_commit_bid_params(account: AccountAddress) -> CommitBidParams {
+        let BidParams {
+            token_id,
+            authorization,
+        } = get_bid_params(account, TOKEN_0);
+        CommitBidParams {
+            token_id,
+            commitment: HashSha2256([0u8; 32]),
+            authorization,
+        }
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_deposit_below_minimum_when_commit_bid_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_self_address(CONTRACT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+        let parameter_bytes = to_bytes(&get_commit_bid_params(ACCOUNT_1));
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = get_sealed_bid_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> =
+            contract_commit_bid(&ctx, &mut host, Amount::from_ccd(5), &mut logger, &crypto);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::DepositTooLow)
+        );
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_voucher_of_other_account_when_commit_bid_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_self_address(CONTRACT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+        let parameter_bytes = to_bytes(&get_commit_bid_params(ACCOUNT_0));
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = get_sealed_bid_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> =
+            contract_commit_bid(&ctx, &mut host, Amount::from_ccd(10), &mut logger, &crypto);
+
+        // Assert
+        claim_eq!(result.expect_err("Should be error"), ContractError::Unauthorized);
+        claim!(host.state().sealed_bids.get(&TOKEN_0).is_none());
+        claim!(logger.logs.is_empty());
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_sealed_bid_auction_when_mint_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        set_mint_context(&mut ctx);
+
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(TOKEN_0);
+        let parameter = MintParams {
+            tokens: get_mint_amounts(&tokens),
+            authorization: MintAuthorization::Voucher {
+                voucher: get_voucher(&tokens),
+                signature: SignatureEd25519([0u8; 64]),
+            },
+            to: None,
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = get_sealed_bid_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> = contract_mint(&ctx, &mut host, Amount::zero(), &mut logger, &crypto);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::SealedBidAuction)
+        );
+    }
+
+    #[concordium_test]
+    #[cfg(feature = "crypto-primitives")]
+    fn given_minting_closed_when_reveal_bid_then_deposit_returned() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(15));
+        let salt = HashSha2256([7u8; 32]);
+        let parameter_bytes = to_bytes(&RevealBidParams {
+            token_id: TOKEN_0,
+            amount: Amount::from_ccd(30),
+            salt,
+        });
+        ctx.set_parameter(&parameter_bytes);
+
+        let crypto = TestCryptoPrimitives::new();
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = get_sealed_bid_state(&mut state_builder);
+        let bid = SealedBid {
+            commitment: crypto.hash_sha2_256(&to_bytes(&(Amount::from_ccd(30), salt))),
+            deposit: Amount::from_ccd(50),
+        };
+        state
+            .commit_sealed_bid(&TOKEN_0, ACCOUNT_1, bid, &mut state_builder)
+            .expect_report("Commit failed");
+        state.minting_closed.insert(TOKEN_0);
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> =
+            contract_reveal_bid(&ctx, &mut host, &mut logger, &crypto);
+
+        // Assert
+        claim!(result.is_ok());
+        claim!(host.state().auctions.get(&TOKEN_0).is_none());
+        claim_eq!(
+            *host.state().pending_returns.get(&ACCOUNT_1).expect_report("No pending return"),
+            Amount::from_ccd(50)
+        );
+    }
+
+    #[concordium_test]
+    fn given_minting_closed_when_place_revealed_bid_then_deposit_returned() {
+        // Arrange
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = get_sealed_bid_state(&mut state_builder);
+        state.minting_closed.insert(TOKEN_0);
+
+        // Act
+        let result = state.place_revealed_bid(
+            &TOKEN_0,
+            ACCOUNT_1,
+            Amount::from_ccd(30),
+            Amount::from_ccd(50),
+        );
+
+        // Assert
+        claim!(result.is_ok());
+        claim!(state.auctions.get(&TOKEN_0).is_none());
+        claim_eq!(
+            *state.pending_returns.get(&ACCOUNT_1).expect_report("No pending return"),
+            Amount::from_ccd(50)
+        );
+    }
+
+    #[concordium_test]
+    fn given_paused_when_reveal_bid_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(15));
+        let parameter_bytes = to_bytes(&RevealBidParams {
+            token_id: TOKEN_0,
+            amount: Amount::from_ccd(30),
+            salt: HashSha2256([7u8; 32]),
+        });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = get_sealed_bid_state(&mut state_builder);
+        let bid = SealedBid {
+            commitment: HashSha2256([0u8; 32]),
+            deposit: Amount::from_ccd(50),
+        };
+        state
+            .commit_sealed_bid(&TOKEN_0, ACCOUNT_1, bid, &mut state_builder)
+            .expect_report("Commit failed");
+        state.paused = true;
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+
+        // Act
+        let result: ContractResult<()> =
+            contract_reveal_bid(&ctx, &mut host, &mut logger, &crypto);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::Paused)
+        );
+        claim!(host.state().sealed_bids.get(&TOKEN_0).is_some());
+    }
+
+    #[concordium_test]
+    #[cfg(feature = "crypto-primitives")]
+    fn given_committed_bids_when_reveal_bid_then_highest_kept_and_rest_returned() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(15));
+        let salt = HashSha2256([7u8; 32]);
+        let parameter_bytes = to_bytes(&RevealBidParams {
+            token_id: TOKEN_0,
+            amount: Amount::from_ccd(30),
+            salt,
+        });
+        ctx.set_parameter(&parameter_bytes);
+
+        let crypto = TestCryptoPrimitives::new();
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = get_sealed_bid_state(&mut state_builder);
+        state
+            .place_bid(&TOKEN_0, ACCOUNT_0, Amount::from_ccd(20))
+            .expect_report("Initial bid failed");
+        let bid = SealedBid {
+            commitment: crypto.hash_sha2_256(&to_bytes(&(Amount::from_ccd(30), salt))),
+            deposit: Amount::from_ccd(50),
+        };
+        state
+            .commit_sealed_bid(&TOKEN_0, ACCOUNT_1, bid, &mut state_builder)
+            .expect_report("Commit failed");
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> =
+            contract_reveal_bid(&ctx, &mut host, &mut logger, &crypto);
+
+        // Assert
+        claim!(result.is_ok());
+        let auction = host.state().auctions.get(&TOKEN_0).expect_report("Auction missing");
+        claim_eq!(auction.highest_bidder, Some(ACCOUNT_1));
+        claim_eq!(auction.highest_bid, Amount::from_ccd(30));
+        claim_eq!(
+            *host.state().pending_returns.get(&ACCOUNT_0).expect_report("No pending return"),
+            Amount::from_ccd(20)
+        );
+        claim_eq!(
+            *host.state().pending_returns.get(&ACCOUNT_1).expect_report("No pending return"),
+            Amount::from_ccd(20)
+        );
+        claim!(host.state().sealed_bids.get(&TOKEN_0).is_none());
+    }
+
+    #[concordium_test]
+    fn given_unrevealed_bid_when_settle_sealed_bid_then_deposit_forfeited() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(20));
+        let parameter_bytes = to_bytes(&SettleSealedBidParams {
+            token_id: TOKEN_0,
+            bidder: ACCOUNT_1,
+        });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = get_sealed_bid_state(&mut state_builder);
+        let bid = SealedBid {
+            commitment: HashSha2256([0u8; 32]),
+            deposit: Amount::from_ccd(100),
+        };
+        state
+            .commit_sealed_bid(&TOKEN_0, ACCOUNT_1, bid, &mut state_builder)
+            .expect_report("Commit failed");
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_settle_sealed_bid(&ctx, &mut host);
+
+        // Assert
+        claim!(result.is_ok());
+        claim_eq!(
+            *host.state().pending_returns.get(&ACCOUNT_0).expect_report("No forfeit"),
+            Amount::from_ccd(10)
+        );
+        claim_eq!(
+            *host.state().pending_returns.get(&ACCOUNT_1).expect_report("No refund"),
+            Amount::from_ccd(90)
+        );
+    }
+
+    fn get_dutch_auction_state<S: HasStateApi>(state_builder: &mut StateBuilder<S>) -> State<S> {
+        let mut state = State::empty(state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        state.tokens.insert(
+            TOKEN_0,
+            TokenInfo {
+                start: Some(Timestamp::from_timestamp_millis(0)),
+                end: Some(Timestamp::from_timestamp_millis(100)),
+                price: Some(DutchPrice {
+                    start_price: Amount::from_ccd(100),
+                    floor_price: Amount::from_ccd(50),
+                }),
+                ..get_token_info(get_token_metadata(), 1)
+            },
+        );
+        state
+    }
+
+    #[concordium_test]
+    fn given_dutch_auction_halfway_when_current_price_then_halfway_to_floor() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(50));
+        let parameter_bytes = to_bytes(&AuctionParams { token_id: TOKEN_0 });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut state_builder = TestStateBuilder::new();
+        let state = get_dutch_auction_state(&mut state_builder);
+        let host = TestHost::new(state, state_builder);
+
+        // Act
+        let result = contract_current_price(&ctx, &host);
+
+        // Assert
+        claim_eq!(result, Ok(Amount::from_ccd(75)));
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_overpayment_when_mint_then_proceeds_recorded_and_rest_refunded() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        set_mint_context(&mut ctx);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(50));
+
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(TOKEN_0);
+        let parameter = MintParams {
+            tokens: get_mint_amounts(&tokens),
+            authorization: MintAuthorization::Voucher {
+                voucher: get_voucher(&tokens),
+                signature: SignatureEd25519([0u8; 64]),
+            },
+            to: None,
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = get_dutch_auction_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        host.set_self_balance(Amount::from_ccd(100));
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> =
+            contract_mint(&ctx, &mut host, Amount::from_ccd(100), &mut logger, &crypto);
+
+        // Assert
+        claim!(result.is_ok());
+        claim_eq!(
+            *host.state().proceeds.get(&ACCOUNT_0).expect_report("No proceeds"),
+            Amount::from_ccd(75)
+        );
+        claim!(host.transfer_occurred(&ACCOUNT_0, Amount::from_ccd(25)));
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_underpayment_when_mint_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        set_mint_context(&mut ctx);
+
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(TOKEN_0);
+        let parameter = MintParams {
+            tokens: get_mint_amounts(&tokens),
+            authorization: MintAuthorization::Voucher {
+                voucher: get_voucher(&tokens),
+                signature: SignatureEd25519([0u8; 64]),
+            },
+            to: None,
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = get_dutch_auction_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> =
+            contract_mint(&ctx, &mut host, Amount::from_ccd(99), &mut logger, &crypto);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::InsufficientPayment)
+        );
+    }
+
+    #[concordium_test]
+    fn given_proceeds_when_withdraw_proceeds_then_transferred_to_recipient() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state.proceeds.insert(ACCOUNT_1, Amount::from_ccd(75));
+        let mut host = TestHost::new(state, state_builder);
+        host.set_self_balance(Amount::from_ccd(75));
+
+        // Act
+        let result: ContractResult<()> = contract_withdraw_proceeds(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim!(result.is_ok());
+        claim!(host.state().proceeds.get(&ACCOUNT_1).is_none());
+        claim!(host.transfer_occurred(&ACCOUNT_1, Amount::from_ccd(75)));
+        claim_eq!(
+            logger.logs,
+            [to_bytes(&ContractEvent::ProceedsWithdrawn(ProceedsWithdrawnEvent {
+                recipient: ACCOUNT_1,
+                amount: Amount::from_ccd(75),
+            }))]
+        );
+    }
+
+    #[concordium_test]
+    fn given_payouts_when_credit_proceeds_then_split_by_share() {
+        // Arrange
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        let recipient = AccountAddress([2u8; 32]);
+        let payouts = [
+            Payout {
+                recipient: ACCOUNT_1,
+                share_bps: 3_333,
+            },
+            Payout {
+                recipient,
+                share_bps: 6_667,
+            },
+        ];
+
+        // Act
+        let result = credit_proceeds(
+            &mut state,
+            &mut logger,
+            TOKEN_0,
+            &payouts,
+            Amount::from_micro_ccd(100),
+        );
+
+        // Assert
+        claim!(result.is_ok());
+        claim_eq!(
+            *state.proceeds.get(&ACCOUNT_1).expect_report("No proceeds"),
+            Amount::from_micro_ccd(33)
+        );
+        claim_eq!(
+            *state.proceeds.get(&recipient).expect_report("No proceeds"),
+            Amount::from_micro_ccd(67)
+        );
+        claim_eq!(logger.logs.len(), 2);
+    }
+
+    #[concordium_test]
+    fn given_shares_not_adding_up_when_init_auction_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+
+        let mut tokens = collections::BTreeMap::new();
+        tokens.insert(
+            TOKEN_0,
+            TokenInfo {
+                payouts: vec![Payout {
+                    recipient: ACCOUNT_1,
+                    share_bps: 9_000,
+                }],
+                ..get_token_info(get_token_metadata(), 1)
+            },
+        );
+        let parameter_bytes = to_bytes(&AuctionInitParams { tokens });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_init_auction(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::InvalidPayoutShares)
+        );
+    }
+
+    #[concordium_test]
+    fn given_state_when_serialized_then_version_is_read_first() {
+        // Arrange
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder);
+
+        // Act
+        let bytes = to_bytes(&state);
+
+        // Assert
+        let version: u32 = from_bytes(&bytes[..4]).expect_report("Should read version");
+        claim_eq!(version, STATE_VERSION);
+    }
+
+    /// Write `state` as the root in the layout of version 6, which still had
+    /// `public_keys` after the 52 bytes up to `collection_mints`.
+    fn write_v6_root(state_api: &mut TestStateApi, state: &State<TestStateApi>) {
+        let mut state_builder = StateBuilder::open(state_api.clone());
+        let public_keys: StateMap<AccountAddress, PublicKeyEd25519, TestStateApi> =
+            state_builder.new_map();
+        let mut bytes = to_bytes(state);
+        bytes.splice(52..52, to_bytes(&public_keys));
+        bytes[..4].copy_from_slice(&to_bytes(&6u32));
+
+        state_api.write_root(state);
+        let mut root = state_api.lookup_entry(&[]).expect_report("Root missing");
+        root.write_all(&bytes).expect_report("Should write root");
+    }
+
+    #[concordium_test]
+    fn given_v1_tokens_when_migrate_from_v1_then_statement_id_defaulted() {
+        // Arrange
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        let mut state_api = state_builder.into_inner();
+        state_api.write_root(&state);
+
+        let mut old: RootTokensV1<TestStateApi> =
+            state_api.read_root().expect_report("Should read tokens");
+        old.tokens.insert(TOKEN_0, get_token_info_v1(get_token_metadata(), 400));
+
+        // Act
+        let result = migrate_from_v1(&mut state_api);
+
+        // Assert
+        claim!(result.is_ok());
+        let new: RootTokensV2<TestStateApi> =
+            state_api.read_root().expect_report("Should read tokens");
+        let version: u32 = state_api.read_root().expect_report("Should read version");
+        claim_eq!(version, 2);
+        let token = new.tokens.get(&TOKEN_0).expect_report("Token missing");
+        claim_eq!(token.max_supply, 400.into());
+        claim!(token.statement_id.is_none());
+    }
+
+    #[concordium_test]
+    fn given_v1_state_when_migrate_state_then_read_with_current_layout() {
+        // Arrange
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        state.mint(&TOKEN_0, 1.into(), &ADDRESS_0, &mut state_builder);
+        let mut state_api = state_builder.into_inner();
+        write_v6_root(&mut state_api, &state);
+
+        // The root of version 1 ends at `proceeds`, before the 28 bytes of
+        // `next_token_id`, `collections`, `minting_closed` and `minters`.
+        let mut root = state_api.lookup_entry(&[]).expect_report("Root missing");
+        let size = root.size().expect_report("Should read size");
+        root.truncate(size - 28).expect_report("Should truncate");
+        root.move_to_start();
+        root.write_all(&to_bytes(&1u32)).expect_report("Should write version");
+
+        let mut v1: RootTokensV1<TestStateApi> =
+            state_api.read_root().expect_report("Should read tokens");
+        v1.tokens.insert(TOKEN_0, get_token_info_v1(get_token_metadata(), 400));
+        v1.tokens.insert(TOKEN_1, get_token_info_v1(get_token_metadata(), 1));
+
+        // Act
+        let result = migrate_state(&mut state_api);
+
+        // Assert
+        claim!(result.is_ok());
+        let migrated: State<TestStateApi> = state_api.read_root().expect_report("Should read state");
+        claim_eq!(migrated.state_version, STATE_VERSION);
+        claim_eq!(migrated.admin, ACCOUNT_0);
+        claim_eq!(migrated.next_token_id, 0);
+        claim!(migrated.collections.iter().next().is_none());
+        claim!(migrated.minting_closed.iter().next().is_none());
+        claim!(migrated.minters.iter().next().is_none());
+
+        let token = migrated.tokens.get(&TOKEN_0).expect_report("Token missing");
+        claim_eq!(token.max_supply, 400.into());
+        claim!(token.statement_id.is_none());
+        drop(token);
+        claim_eq!(
+            migrated.balance(&TOKEN_0, &ADDRESS_0).expect_report("Balance missing"),
+            1.into()
+        );
+        claim!(migrated.tokens.get(&TOKEN_1).is_some());
+    }
+
+    #[concordium_test]
+    fn given_v4_state_when_migrate_state_then_minters_indexed() {
+        // Arrange
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state.minted.insert((ACCOUNT_0, TOKEN_0), 1.into());
+        state.minted.insert((ACCOUNT_1, TOKEN_0), 2.into());
+        let mut state_api = state_builder.into_inner();
+        write_v6_root(&mut state_api, &state);
+
+        // The root of version 4 ends at `minting_closed`, before the 8 bytes
+        // of `minters`.
+        let mut root = state_api.lookup_entry(&[]).expect_report("Root missing");
+        let size = root.size().expect_report("Should read size");
+        root.truncate(size - 8).expect_report("Should truncate");
+        root.move_to_start();
+        root.write_all(&to_bytes(&4u32)).expect_report("Should write version");
+
+        // Act
+        let result = migrate_state(&mut state_api);
+
+        // Assert
+        claim!(result.is_ok());
+        let migrated: State<TestStateApi> = state_api.read_root().expect_report("Should read state");
+        claim_eq!(migrated.state_version, STATE_VERSION);
+        let minters = migrated.minters.get(&TOKEN_0).expect_report("Minters missing");
+        claim!(minters.accounts.contains(&ACCOUNT_0));
+        claim!(minters.accounts.contains(&ACCOUNT_1));
+        claim!(migrated.minters.get(&TOKEN_1).is_none());
+    }
+
+    #[concordium_test]
+    fn given_v5_state_when_migrate_state_then_collection_cap_moved_to_collection() {
+        // Arrange
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        let mut state_api = state_builder.into_inner();
+        write_v6_root(&mut state_api, &state);
+
+        // The root of version 5 has the layout of version 6, only the tokens
+        // and collections are stored differently.
+        let mut root = state_api.lookup_entry(&[]).expect_report("Root missing");
+        root.write_all(&to_bytes(&5u32)).expect_report("Should write version");
+
+        let mut v5: RootTokensV2<TestStateApi> =
+            state_api.read_root().expect_report("Should read tokens");
+        for (token_id, max_tokens) in [(TOKEN_0, 3), (TOKEN_1, 2)] {
+            let mut token: TokenInfoV2 = get_token_info_v1(get_token_metadata(), 400).into();
+            token.collection = Some(CollectionCapV1 {
+                name: "dinos".to_string(),
+                max_tokens_per_account: max_tokens,
+            });
+            v5.tokens.insert(token_id, token);
+        }
+        let mut state_builder = StateBuilder::open(state_api.clone());
+        let mut collection_tokens = state_builder.new_set();
+        collection_tokens.insert(TOKEN_0);
+        collection_tokens.insert(TOKEN_1);
+        let mut collections: RootCollections<TestStateApi, CollectionV3<TestStateApi>> =
+            state_api.read_root().expect_report("Should read collections");
+        collections.collections.insert("dinos".to_string(), CollectionV3 {
+            metadata: None,
+            tokens: collection_tokens,
+        });
+
+        // Act
+        let result = migrate_state(&mut state_api);
+
+        // Assert
+        claim!(result.is_ok());
+        let migrated: State<TestStateApi> = state_api.read_root().expect_report("Should read state");
+        claim_eq!(migrated.state_version, STATE_VERSION);
+        let collection =
+            migrated.collections.get(&"dinos".to_string()).expect_report("Collection missing");
+        claim_eq!(collection.max_tokens_per_account, Some(2));
+        claim!(collection.tokens.contains(&TOKEN_0));
+        claim!(collection.tokens.contains(&TOKEN_1));
+        drop(collection);
+        let token = migrated.tokens.get(&TOKEN_0).expect_report("Token missing");
+        let cap = token.collection.as_ref().expect_report("Collection missing");
+        claim_eq!(cap.max_tokens_per_account, Some(3));
+    }
+
+    #[concordium_test]
+    fn given_v6_state_when_migrate_state_then_public_keys_dropped() {
+        // Arrange
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state.nonces.insert(ACCOUNT_0, 3);
+        let mut state_api = state_builder.into_inner();
+        write_v6_root(&mut state_api, &state);
+
+        let mut v6: RootPublicKeys<TestStateApi> =
+            state_api.read_root().expect_report("Should read public keys");
+        v6.public_keys.insert(ACCOUNT_0, PublicKeyEd25519([7u8; 32]));
+
+        // Act
+        let result = migrate_state(&mut state_api);
+
+        // Assert
+        claim!(result.is_ok());
+        let migrated: State<TestStateApi> = state_api.read_root().expect_report("Should read state");
+        claim_eq!(migrated.state_version, STATE_VERSION);
+        claim_eq!(migrated.admin, ACCOUNT_0);
+        claim_eq!(migrated.nonces.get(&ACCOUNT_0).map(|nonce| *nonce), Some(3));
+        claim!(migrated.tokens.get(&TOKEN_1).is_some());
+        claim!(migrated.minters.iter().next().is_none());
+    }
+
+    #[concordium_test]
+    fn given_current_state_when_migrate_state_then_unchanged() {
+        // Arrange
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder);
+        let mut state_api = state_builder.into_inner();
+        state_api.write_root(&state);
+
+        // Act
+        let result = migrate_state(&mut state_api);
+
+        // Assert
+        claim!(result.is_ok());
+        let migrated: State<TestStateApi> = state_api.read_root().expect_report("Should read state");
+        claim_eq!(migrated.state_version, STATE_VERSION);
+        claim!(migrated.tokens.get(&TOKEN_1).is_some());
+    }
+
+    #[concordium_test]
+    fn given_existing_tokens_when_create_auctions_then_free_ids_returned() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+
+        let metadata = get_token_metadata();
+        let parameter = CreateAuctionsParams {
+            tokens: vec![
+                get_token_info(metadata.clone(), 10),
+                get_token_info(metadata.clone(), 20),
+            ],
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result = contract_create_auctions(&ctx, &mut host, &mut logger);
+
+        // Assert
+        let token_ids = result.expect_report("Should create auctions");
+        claim_eq!(token_ids, vec![TokenIdU32(1), TokenIdU32(2)]);
+        claim_eq!(host.state().next_token_id, 3);
+
+        let token = host.state().tokens.get(&TokenIdU32(2)).expect_report("Token not inserted");
+        claim_eq!(token.max_supply, 20.into());
+        claim_eq!(logger.logs.len(), 2);
+    }
+
+    #[concordium_test]
+    fn given_collection_tokens_when_view_collection_then_metadata_and_tokens_returned() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+
+        let metadata = get_token_metadata();
+        let mut token_info = get_token_info(metadata.clone(), 10);
+        token_info.collection = Some(CollectionCap {
+            name: "dinos".to_string(),
+            max_tokens_per_account: Some(1),
+        });
+        let mut uncapped_info = token_info.clone();
+        uncapped_info.collection = Some(CollectionCap {
+            name: "dinos".to_string(),
+            max_tokens_per_account: None,
+        });
+        let parameter_bytes = to_bytes(&CreateAuctionsParams {
+            tokens: vec![token_info, get_token_info(metadata.clone(), 10), uncapped_info],
+        });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        let mut host = TestHost::new(state, state_builder);
+        contract_create_auctions(&ctx, &mut host, &mut logger).expect_report("Should create auctions");
+
+        let parameter_bytes = to_bytes(&SetCollectionMetadataParams {
+            name: "dinos".to_string(),
+            metadata: metadata.clone(),
+        });
+        ctx.set_parameter(&parameter_bytes);
+        contract_set_collection_metadata(&ctx, &mut host, &mut logger)
+            .expect_report("Should set metadata");
+
+        let parameter_bytes = to_bytes(&ViewCollectionParams {
+            name: "dinos".to_string(),
+            page: ViewPageParams { cursor: 0, limit: 10 },
+        });
+        ctx.set_parameter(&parameter_bytes);
+
+        // Act
+        let result = contract_view_collection(&ctx, &host).expect_report("View failed");
+
+        // Assert
+        claim_eq!(result.tokens, vec![TokenIdU32(0), TokenIdU32(2)]);
+        claim!(result.next.is_none());
+        claim_eq!(result.max_tokens_per_account, Some(1));
+        let collection_metadata = result.metadata.expect_report("Metadata missing");
+        claim_eq!(collection_metadata.url, metadata.url);
+    }
+
+    #[concordium_test]
+    fn given_conflicting_collection_cap_when_create_auctions_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+
+        let metadata = get_token_metadata();
+        let mut first = get_token_info(metadata.clone(), 10);
+        first.collection = Some(CollectionCap {
+            name: "dinos".to_string(),
+            max_tokens_per_account: Some(1),
+        });
+        let mut second = first.clone();
+        second.collection = Some(CollectionCap {
+            name: "dinos".to_string(),
+            max_tokens_per_account: Some(2),
+        });
+        let parameter_bytes = to_bytes(&CreateAuctionsParams {
+            tokens: vec![first, second],
+        });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result = contract_create_auctions(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::CollectionCapMismatch)
+        );
+    }
+
+    #[concordium_test]
+    fn given_unknown_collection_when_view_collection_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+
+        let parameter_bytes = to_bytes(&ViewCollectionParams {
+            name: "dinos".to_string(),
+            page: ViewPageParams { cursor: 0, limit: 10 },
+        });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder);
+        let host = TestHost::new(state, state_builder);
+
+        // Act
+        let result = contract_view_collection(&ctx, &host);
+
+        // Assert
+        claim_eq!(
+            result.err(),
+            Some(ContractError::Custom(CustomContractError::CollectionNotFound))
+        );
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_voucher_for_other_statement_when_mint_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        set_mint_context(&mut ctx);
+
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(TOKEN_0);
+        let parameter = MintParams {
+            tokens: get_mint_amounts(&tokens),
+            authorization: MintAuthorization::Voucher {
+                voucher: get_voucher(&tokens),
+                signature: SignatureEd25519([0u8; 64]),
+            },
+            to: None,
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        state.tokens.insert(
+            TOKEN_0,
+            TokenInfo {
+                statement_id: Some(String::from("age-over-18")),
+                ..get_token_info(get_token_metadata(), 1)
+            },
+        );
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> =
+            contract_mint(&ctx, &mut host, Amount::zero(), &mut logger, &crypto);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::StatementMismatch)
+        );
+    }
+
+    #[concordium_test]
+    fn given_free_policy_when_owner_transfers_then_ok() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+
+        let transfer = Transfer {
+            token_id: TOKEN_0,
+            amount: ContractTokenAmount::from(1),
+            from: ADDRESS_0,
+            to: Receiver::from_account(ACCOUNT_1),
+            data: AdditionalData::empty(),
+        };
+        let parameter = TransferParams::from(vec![transfer]);
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state
+            .tokens
+            .entry(TOKEN_0)
+            .and_modify(|token| token.transfer_policy = TransferPolicy::Free);
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_transfer(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim!(result.is_ok());
+        claim_eq!(host.state().balance(&TOKEN_0, &ADDRESS_0).expect_report("Token missing"), 0.into());
+        claim_eq!(host.state().balance(&TOKEN_0, &ADDRESS_1).expect_report("Token missing"), 1.into());
+        claim_eq!(
+            logger.logs,
+            vec![to_bytes(&Cis2Event::Transfer(TransferEvent {
+                token_id: TOKEN_0,
+                amount: ContractTokenAmount::from(1),
+                from: ADDRESS_0,
+                to: ADDRESS_1,
+            }))],
+            "Expected a transfer event"
+        );
+    }
+
+    #[concordium_test]
+    fn given_operator_only_policy_when_owner_transfers_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+
+        let transfer = Transfer {
+            token_id: TOKEN_0,
+            amount: ContractTokenAmount::from(1),
+            from: ADDRESS_0,
+            to: Receiver::from_account(ACCOUNT_1),
+            data: AdditionalData::empty(),
+        };
+        let parameter = TransferParams::from(vec![transfer]);
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state
+            .tokens
+            .entry(TOKEN_0)
+            .and_modify(|token| token.transfer_policy = TransferPolicy::OperatorOnly);
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_transfer(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim_eq!(result.expect_err("Should be error"), ContractError::Unauthorized);
+    }
+
+    #[concordium_test]
+    fn given_operator_only_policy_when_operator_transfers_to_contract_then_hook_invoked() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+
+        let entrypoint = OwnedEntrypointName::new_unchecked("onReceivingCIS2".to_string());
+        let transfer = Transfer {
+            token_id: TOKEN_0,
+            amount: ContractTokenAmount::from(1),
+            from: ADDRESS_0,
+            to: Receiver::from_contract(CONTRACT_0, entrypoint.clone()),
+            data: AdditionalData::empty(),
+        };
+        let parameter = TransferParams::from(vec![transfer]);
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state
+            .tokens
+            .entry(TOKEN_0)
+            .and_modify(|token| token.transfer_policy = TransferPolicy::OperatorOnly);
+        state.add_operator(&ADDRESS_0, &ADDRESS_1, &mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        host.setup_mock_entrypoint(CONTRACT_0, entrypoint, MockFn::returning_ok(()));
+
+        // Act
+        let result: ContractResult<()> = contract_transfer(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim!(result.is_ok());
+        claim_eq!(
+            host.state()
+                .balance(&TOKEN_0, &Address::Contract(CONTRACT_0))
+                .expect_report("Token missing"),
+            1.into()
+        );
+    }
+
+    #[concordium_test]
+    fn given_sender_is_admin_when_add_verify_key_then_key_active() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+
+        let key = PublicKeyEd25519([1u8; 32]);
+        let parameter_bytes = to_bytes(&VerifyKeyParams { key });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_add_verify_key(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim!(result.is_ok());
+        claim!(host.state().verify_keys.contains(&key));
+        claim!(host.state().verify_keys.contains(&PublicKeyEd25519([0u8; 32])));
+        claim_eq!(
+            logger.logs,
+            vec![to_bytes(&ContractEvent::VerifyKeyChanged(VerifyKeyChangedEvent {
+                key,
+                update: VerifyKeyUpdate::Added,
+            }))]
+        );
+    }
+
+    #[concordium_test]
+    fn given_sender_is_not_admin_when_add_verify_key_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_owner(ACCOUNT_0);
+
+        let parameter_bytes = to_bytes(&VerifyKeyParams {
+            key: PublicKeyEd25519([1u8; 32]),
+        });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_add_verify_key(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim_eq!(result.expect_err("Should be error"), ContractError::Unauthorized);
+    }
+
+    #[concordium_test]
+    fn given_active_key_when_rotate_verify_key_then_replaced() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+
+        let old = PublicKeyEd25519([0u8; 32]);
+        let new = PublicKeyEd25519([1u8; 32]);
+        let parameter_bytes = to_bytes(&RotateVerifyKeyParams { old, new });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder, ACCOUNT_0, old);
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_rotate_verify_key(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim!(result.is_ok());
+        claim!(!host.state().verify_keys.contains(&old));
+        claim!(host.state().verify_keys.contains(&new));
+        claim_eq!(logger.logs.len(), 2, "Expected a revoke and an add event");
+    }
+
+    #[concordium_test]
+    fn given_unknown_key_when_revoke_verify_key_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+
+        let parameter_bytes = to_bytes(&VerifyKeyParams {
+            key: PublicKeyEd25519([1u8; 32]),
+        });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_revoke_verify_key(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::VerifyKeyNotActive)
+        );
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_revoked_key_when_mint_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        set_mint_context(&mut ctx);
+
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(TOKEN_0);
+        let parameter = MintParams {
+            tokens: get_mint_amounts(&tokens),
+            authorization: MintAuthorization::Voucher {
+                voucher: get_voucher(&tokens),
+                signature: SignatureEd25519([0u8; 64]),
+            },
+            to: None,
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let revoked = PublicKeyEd25519([0u8; 32]);
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state
+            .add_verify_key(PublicKeyEd25519([1u8; 32]))
+            .expect_report("Key not added");
+        state.revoke_verify_key(&revoked).expect_report("Key not revoked");
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(move |key, _, _| key == revoked);
+
+        // Act
+        let result: ContractResult<()> = contract_mint(&ctx, &mut host, Amount::zero(), &mut logger, &crypto);
+
+        // Assert
+        claim_eq!(result.expect_err("Should be error"), ContractError::Unauthorized);
+    }
+
+    #[concordium_test]
+    fn given_proposed_admin_when_accept_admin_then_admin_changed() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+
+        let parameter_bytes = to_bytes(&ProposeAdminParams { admin: ACCOUNT_1 });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        let mut host = TestHost::new(state, state_builder);
+
+        contract_propose_admin(&ctx, &mut host, &mut logger).expect_report("Propose failed");
+        ctx.set_sender(ADDRESS_1);
+
+        // Act
+        let result: ContractResult<()> = contract_accept_admin(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim!(result.is_ok());
+        claim_eq!(host.state().admin, ACCOUNT_1);
+        claim!(host.state().proposed_admin.is_none());
+        claim_eq!(
+            logger.logs[1],
+            to_bytes(&ContractEvent::AdminChanged(AdminChangedEvent {
+                previous: ACCOUNT_0,
+                admin: ACCOUNT_1,
+            }))
+        );
+    }
+
+    #[concordium_test]
+    fn given_not_proposed_when_accept_admin_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_owner(ACCOUNT_0);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_accept_admin(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim_eq!(result.expect_err("Should be error"), ContractError::Unauthorized);
+        claim_eq!(host.state().admin, ACCOUNT_0);
+    }
+
+    #[concordium_test]
+    fn given_granted_auction_manager_when_init_auction_then_ok() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+
+        let parameter_bytes = to_bytes(&RoleParams {
+            address: ADDRESS_1,
+            role: Role::AuctionManager,
+        });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        let mut host = TestHost::new(state, state_builder);
+
+        contract_grant_role(&ctx, &mut host, &mut logger).expect_report("Grant failed");
+
+        let mut tokens = collections::BTreeMap::new();
+        tokens.insert(TOKEN_0, get_token_info(get_token_metadata(), 400));
+        let parameter_bytes = to_bytes(&AuctionInitParams { tokens });
+        ctx.set_parameter(&parameter_bytes);
+        ctx.set_sender(ADDRESS_1);
+
+        // Act
+        let result: ContractResult<()> = contract_init_auction(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim!(result.is_ok());
+        claim!(host.state().has_role(&ADDRESS_1, Role::AuctionManager));
+        claim!(!host.state().has_role(&ADDRESS_1, Role::Admin));
+        claim!(host.state().tokens.get(&TOKEN_0).is_some());
+    }
+
+    #[concordium_test]
+    fn given_admin_role_when_grant_role_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_owner(ACCOUNT_0);
+
+        let parameter_bytes = to_bytes(&RoleParams {
+            address: ADDRESS_1,
+            role: Role::Admin,
+        });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_grant_role(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::InvalidRole)
+        );
+    }
+
+    #[concordium_test]
+    fn given_sender_is_admin_when_set_paused_then_paused() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+
+        let parameter_bytes = to_bytes(&SetPausedParams { paused: true });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_set_paused(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim!(result.is_ok());
+        claim!(host.state().paused);
+        claim_eq!(
+            logger.logs,
+            [to_bytes(&ContractEvent::PausedChanged(PausedChangedEvent { paused: true }))]
+        );
+    }
+
+    #[concordium_test]
+    fn given_sender_is_not_admin_when_set_paused_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+
+        let parameter_bytes = to_bytes(&SetPausedParams { paused: true });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_set_paused(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim_eq!(result.expect_err("Should be error"), ContractError::Unauthorized);
+        claim!(!host.state().paused);
+    }
+
+    #[concordium_test]
+    fn given_paused_when_burn_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+
+        let parameter_bytes = to_bytes(&BurnParams { token_id: TOKEN_0, amount: 1.into() });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state.paused = true;
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_burn(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::Paused)
+        );
+        claim!(host.state().balance(&TOKEN_0, &ADDRESS_0).expect_report("Token missing") > 0.into());
+    }
+
+    #[concordium_test]
+    fn given_paused_when_mint_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        set_mint_context(&mut ctx);
+
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(TOKEN_0);
+        let parameter = MintParams {
+            tokens: get_mint_amounts(&tokens),
+            authorization: MintAuthorization::Voucher {
+                voucher: get_voucher(&tokens),
+                signature: SignatureEd25519([0u8; 64]),
+            },
+            to: None,
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state.paused = true;
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+
+        // Act
+        let result: ContractResult<()> = contract_mint(&ctx, &mut host, Amount::zero(), &mut logger, &crypto);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::Paused)
+        );
+        claim_eq!(
+            host.state().get_circulating_supply(&TOKEN_0).expect_report("Token missing"),
+            1.into()
+        );
+    }
+
+    #[concordium_test]
+    fn given_paused_when_transfer_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+
+        let parameter_bytes = to_bytes(&TransferParameter::from(vec![Transfer {
+            token_id: TOKEN_0,
+            amount: 1.into(),
+            from: ADDRESS_0,
+            to: Receiver::from_account(ACCOUNT_1),
+            data: AdditionalData::empty(),
+        }]));
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state.paused = true;
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_transfer(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::Paused)
+        );
+        claim_eq!(
+            host.state().balance(&TOKEN_0, &ADDRESS_0).expect_report("Token missing"),
+            1.into()
+        );
+    }
+
+    #[concordium_test]
+    fn given_paused_when_update_operator_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+
+        let parameter_bytes = to_bytes(&UpdateOperatorParams(vec![UpdateOperator {
+            update: OperatorUpdate::Add,
+            operator: ADDRESS_1,
+        }]));
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state.paused = true;
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_update_operator(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::Paused)
+        );
+        claim!(!host.state().is_operator(&ADDRESS_1, &ADDRESS_0));
+    }
+
+    #[concordium_test]
+    fn given_paused_when_close_auction_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+
+        let parameter_bytes = to_bytes(&AuctionParams { token_id: TOKEN_0 });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state
+            .place_bid(&TOKEN_0, ACCOUNT_1, Amount::from_ccd(20))
+            .expect_report("Bid failed");
+        state.paused = true;
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_close_auction(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::Paused)
+        );
+        claim!(!host.state().auctions.get(&TOKEN_0).expect_report("Auction missing").closed);
+    }
+
+    #[concordium_test]
+    fn given_paused_when_bid_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+
+        let parameter_bytes = to_bytes(&BidParams {
+            token_id: TOKEN_0,
+            authorization: MintAuthorization::Allowlist { proof: Vec::new() },
+        });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state.paused = true;
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+
+        // Act
+        let result: ContractResult<()> = contract_bid(&ctx, &mut host, Amount::from_ccd(20), &crypto);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::Paused)
+        );
+        claim!(host.state().auctions.get(&TOKEN_0).is_none());
+    }
+
+    #[concordium_test]
+    fn given_paused_when_balance_of_then_ok() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+
+        let parameter = BalanceOfQueryParams {
+            queries: vec![BalanceOfQuery {
+                token_id: TOKEN_0,
+                address: ADDRESS_0,
+            }],
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state.paused = true;
+        let host = TestHost::new(state, state_builder);
+
+        // Act
+        let result = contract_balance_of(&ctx, &host);
+
+        // Assert
+        claim!(result.is_ok());
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_auction_not_started_when_mint_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        set_mint_context(&mut ctx);
+
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(TOKEN_0);
+        let parameter = MintParams {
+            tokens: get_mint_amounts(&tokens),
+            authorization: MintAuthorization::Voucher {
+                voucher: get_voucher(&tokens),
+                signature: SignatureEd25519([0u8; 64]),
+            },
+            to: None,
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state.tokens.insert(
+            TOKEN_0,
+            TokenInfo {
+                start: Some(Timestamp::from_timestamp_millis(10)),
+                ..get_token_info(get_token_metadata(), 400)
+            },
+        );
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> = contract_mint(&ctx, &mut host, Amount::zero(), &mut logger, &crypto);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::AuctionNotStarted)
+        );
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_auction_ended_when_bid_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_self_address(CONTRACT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+
+        let parameter_bytes = to_bytes(&get_bid_params(ACCOUNT_1, TOKEN_0));
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state.tokens.insert(
+            TOKEN_0,
+            TokenInfo {
+                end: Some(Timestamp::from_timestamp_millis(10)),
+                ..get_token_info(get_token_metadata(), 400)
+            },
+        );
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> = contract_bid(&ctx, &mut host, Amount::from_ccd(10), &crypto);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::AuctionEnded)
+        );
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_price_times_amount_overflows_when_mint_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        set_mint_context(&mut ctx);
+
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(TOKEN_0);
+        let mut amounts = collections::BTreeMap::new();
+        amounts.insert(TOKEN_0, TokenAmountU64(u64::MAX));
+        let parameter = MintParams {
+            tokens: amounts,
+            authorization: MintAuthorization::Voucher {
+                voucher: get_voucher(&tokens),
+                signature: SignatureEd25519([0u8; 64]),
+            },
+            to: None,
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = get_dutch_auction_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> =
+            contract_mint(&ctx, &mut host, Amount::from_ccd(100), &mut logger, &crypto);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::PriceOverflow)
+        );
+    }
+
+    #[concordium_test]
+    fn given_priced_token_when_update_auction_window_without_bounds_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(5));
+
+        let parameter_bytes = to_bytes(&AuctionWindowParams {
+            token_id: TOKEN_0,
+            start: None,
+            end: Some(Timestamp::from_timestamp_millis(100)),
+        });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = get_dutch_auction_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> =
+            contract_update_auction_window(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::InvalidAuctionWindow)
+        );
+        let token = host.state().tokens.get(&TOKEN_0).expect_report("Token missing");
+        claim_eq!(token.start, Some(Timestamp::from_timestamp_millis(0)));
+    }
+
+    #[concordium_test]
+    fn given_active_window_when_update_auction_window_then_updated() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(5));
+
+        let start = Some(Timestamp::from_timestamp_millis(0));
+        let end = Some(Timestamp::from_timestamp_millis(20));
+        let parameter_bytes = to_bytes(&AuctionWindowParams {
+            token_id: TOKEN_0,
+            start,
+            end,
+        });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state.tokens.insert(
+            TOKEN_0,
+            TokenInfo {
+                end: Some(Timestamp::from_timestamp_millis(10)),
+                ..get_token_info(get_token_metadata(), 400)
+            },
+        );
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> =
+            contract_update_auction_window(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim!(result.is_ok());
+        let token = host.state().tokens.get(&TOKEN_0).expect_report("Token missing");
+        claim_eq!(token.start, start);
+        claim_eq!(token.end, end);
+        claim_eq!(
+            logger.logs,
+            [to_bytes(&ContractEvent::AuctionWindowUpdated(AuctionWindowUpdatedEvent {
+                token_id: TOKEN_0,
+                start,
+                end,
+            }))]
+        );
+    }
+
+    #[concordium_test]
+    fn given_ended_window_when_update_auction_window_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(10));
+
+        let parameter_bytes = to_bytes(&AuctionWindowParams {
+            token_id: TOKEN_0,
+            start: None,
+            end: Some(Timestamp::from_timestamp_millis(20)),
+        });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state.tokens.insert(
+            TOKEN_0,
+            TokenInfo {
+                end: Some(Timestamp::from_timestamp_millis(10)),
+                ..get_token_info(get_token_metadata(), 400)
+            },
+        );
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> =
+            contract_update_auction_window(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::AuctionEnded)
+        );
+    }
+
+    #[concordium_test]
+    fn given_address_without_balances_when_view_holders_then_not_listed() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+
+        let parameter_bytes = to_bytes(&ViewPageParams { cursor: 0, limit: 10 });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state.add_operator(&ADDRESS_1, &ADDRESS_0, &mut state_builder);
+        let host = TestHost::new(state, state_builder);
+
+        // Act
+        let result = contract_view_holders(&ctx, &host).expect_report("View failed");
+
+        // Assert
+        claim_eq!(result.holders.len(), 1);
+        claim_eq!(result.holders[0].0, ADDRESS_0);
+        claim!(result.next.is_none());
+    }
+
+    #[concordium_test]
+    fn given_more_holders_than_limit_when_view_holders_then_paginated() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+
+        let parameter_bytes = to_bytes(&ViewPageParams { cursor: 0, limit: 1 });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state.mint(&TOKEN_0, 1.into(), &ADDRESS_1, &mut state_builder);
+        let host = TestHost::new(state, state_builder);
+
+        // Act
+        let first = contract_view_holders(&ctx, &host).expect_report("First page failed");
+
+        let parameter_bytes = to_bytes(&ViewPageParams {
+            cursor: first.next.expect_report("Expected a next page"),
+            limit: 1,
+        });
+        ctx.set_parameter(&parameter_bytes);
+        let second = contract_view_holders(&ctx, &host).expect_report("Second page failed");
+
+        // Assert
+        claim_eq!(first.holders.len(), 1);
+        claim_eq!(second.holders.len(), 1);
+        claim!(second.next.is_none());
+        claim!(first.holders[0].0 != second.holders[0].0);
+    }
+
+    #[concordium_test]
+    fn given_tokens_when_view_tokens_then_supplies_returned() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+
+        let parameter_bytes = to_bytes(&ViewPageParams { cursor: 0, limit: 10 });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder);
+        let host = TestHost::new(state, state_builder);
+
+        // Act
+        let result = contract_view_tokens(&ctx, &host).expect_report("View failed");
+
+        // Assert
+        claim!(result.next.is_none());
+        claim_eq!(result.tokens.len(), 2);
+        let token = result
+            .tokens
+            .iter()
+            .find(|token| token.token_id == TOKEN_0)
+            .expect_report("TOKEN_0 missing");
+        claim_eq!(token.max_supply, 400.into());
+        claim_eq!(token.circulating_supply, 1.into());
+    }
+
+    #[concordium_test]
+    fn given_unknown_address_when_view_account_then_empty() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+
+        let parameter_bytes = to_bytes(&ADDRESS_1);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder);
+        let host = TestHost::new(state, state_builder);
+
+        // Act
+        let result = contract_view_account(&ctx, &host).expect_report("View failed");
+
+        // Assert
+        claim!(result.balances.is_empty());
+        claim!(result.operators.is_empty());
+    }
+
+    #[concordium_test]
+    fn given_more_holders_than_limit_when_burn_auction_then_purged_in_batches() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(TOKEN_0);
+        let parameter_bytes = to_bytes(&ActionBurnParams { tokens, limit: 1 });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state.mint(&TOKEN_0, 1.into(), &ADDRESS_1, &mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let first: ContractResult<()> = contract_burn_auction(&ctx, &mut host, &mut logger);
+        let holders_left = host.state().has_holders(&TOKEN_0);
+        let second: ContractResult<()> = contract_burn_auction(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim!(first.is_ok());
+        claim!(holders_left, "Expected a holder left after the first batch");
+        claim!(second.is_ok());
+        claim!(!host.state().has_holders(&TOKEN_0));
+        claim!(host.state().tokens.get(&TOKEN_0).is_none());
+        claim_eq!(
+            logger.logs,
+            [
+                to_bytes(&ContractEvent::AuctionRemoved(AuctionRemovedEvent {
+                    token_id: TOKEN_0,
+                })),
+                to_bytes(&ContractEvent::Cis2(Cis2Event::Burn(BurnEvent {
+                    token_id: TOKEN_0,
+                    amount: 1.into(),
+                    owner: ADDRESS_0,
+                }))),
+                to_bytes(&ContractEvent::Cis2(Cis2Event::Burn(BurnEvent {
+                    token_id: TOKEN_0,
+                    amount: 1.into(),
+                    owner: ADDRESS_1,
+                }))),
+            ],
+            "Expected a single removal event and a burn per holder across batches"
+        );
+        for address in [ADDRESS_0, ADDRESS_1] {
+            let address_state = host.state().state.get(&address).expect_report("Address missing");
+            claim!(address_state.balances.get(&TOKEN_0).is_none());
+        }
+    }
+
+    #[concordium_test]
+    fn given_minted_token_when_burn_auction_then_mint_counters_cleared() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+
+        let mut tokens = collections::BTreeSet::new();
+        tokens.insert(TOKEN_0);
+        let parameter_bytes = to_bytes(&ActionBurnParams { tokens, limit: 10 });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        let token = TokenInfo {
+            collection: Some(CollectionCap {
+                name: "dinos".to_string(),
+                max_tokens_per_account: Some(2),
+            }),
+            ..get_token_info(get_token_metadata(), 400)
+        };
+        state
+            .record_mint(ACCOUNT_0, &TOKEN_0, 1.into(), &token, &mut state_builder)
+            .expect_report("Mint of burned token failed");
+        state
+            .record_mint(ACCOUNT_0, &TOKEN_1, 1.into(), &token, &mut state_builder)
+            .expect_report("Mint of kept token failed");
+        let mut host = TestHost::new(state, state_builder);
+
+        // Act
+        let result: ContractResult<()> = contract_burn_auction(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim!(result.is_ok());
+        let state = host.state();
+        claim!(!state.has_minters(&TOKEN_0));
+        claim!(state.minted.get(&(ACCOUNT_0, TOKEN_0)).is_none());
+        claim!(state.minted.get(&(ACCOUNT_0, TOKEN_1)).is_some());
+        claim_eq!(
+            state.collection_mints.get(&(ACCOUNT_0, "dinos".to_string())).map(|taken| *taken),
+            Some(1)
+        );
+    }
+
+    #[concordium_test]
+    fn given_holders_not_purged_when_init_auction_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state.tokens.remove(&TOKEN_0);
+        let mut host = TestHost::new(state, state_builder);
+
+        let mut tokens = collections::BTreeMap::new();
+        tokens.insert(TOKEN_0, get_token_info(get_token_metadata(), 400));
+        let parameter_bytes = to_bytes(&AuctionInitParams { tokens });
+        ctx.set_parameter(&parameter_bytes);
+
+        // Act
+        let result: ContractResult<()> = contract_init_auction(&ctx, &mut host, &mut logger);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::HoldersNotPurged)
+        );
+    }
+
+    #[concordium_test]
+    fn given_transfer_when_transfer_then_holder_index_updated() {
+        // Arrange
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+
+        // Act
+        let result =
+            state.transfer(&TOKEN_0, 1.into(), &ADDRESS_0, &ADDRESS_1, &mut state_builder);
+
+        // Assert
+        claim!(result.is_ok());
+        let holders = state.holders.get(&TOKEN_0).expect_report("Holders missing");
+        claim!(!holders.contains(&ADDRESS_0));
+        claim!(holders.contains(&ADDRESS_1));
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_amount_when_mint_then_balance_increased_by_amount() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        set_mint_context(&mut ctx);
+
+        let mut voucher_tokens = collections::BTreeSet::new();
+        voucher_tokens.insert(TOKEN_0);
+        let mut tokens = collections::BTreeMap::new();
+        tokens.insert(TOKEN_0, 3.into());
+        let parameter = MintParams {
+            tokens,
+            authorization: MintAuthorization::Voucher {
+                voucher: get_voucher(&voucher_tokens),
+                signature: SignatureEd25519([0u8; 64]),
+            },
+            to: None,
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> = contract_mint(&ctx, &mut host, Amount::zero(), &mut logger, &crypto);
+
+        // Assert
+        claim!(result.is_ok());
+        claim_eq!(
+            host.state().balance(&TOKEN_0, &ADDRESS_0).expect_report("Token missing"),
+            4.into()
+        );
+        claim_eq!(
+            host.state().get_circulating_supply(&TOKEN_0).expect_report("Token missing"),
+            4.into()
+        );
+        claim_eq!(
+            logger.logs[0],
+            to_bytes(&Cis2Event::Mint(MintEvent {
+                token_id: TOKEN_0,
+                amount: ContractTokenAmount::from(3),
+                owner: ADDRESS_0,
+            }))
+        );
+    }
+
+    #[concordium_test]
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_amount_above_account_cap_when_mint_then_error() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        set_mint_context(&mut ctx);
+
+        let mut voucher_tokens = collections::BTreeSet::new();
+        voucher_tokens.insert(TOKEN_0);
+        let mut tokens = collections::BTreeMap::new();
+        tokens.insert(TOKEN_0, 3.into());
+        let parameter = MintParams {
+            tokens,
+            authorization: MintAuthorization::Voucher {
+                voucher: get_voucher(&voucher_tokens),
+                signature: SignatureEd25519([0u8; 64]),
+            },
+            to: None,
+        };
+        let parameter_bytes = to_bytes(&parameter);
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state.tokens.insert(
+            TOKEN_0,
+            TokenInfo {
+                max_per_account: 2.into(),
+                ..get_token_info(get_token_metadata(), 400)
+            },
+        );
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        // Act
+        let result: ContractResult<()> = contract_mint(&ctx, &mut host, Amount::zero(), &mut logger, &crypto);
+
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::AccountCapReached)
+        );
+    }
+
+    #[concordium_test]
+    fn given_partial_amount_when_transfer_then_both_hold_token() {
+        // Arrange
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        state.mint(&TOKEN_0, 2.into(), &ADDRESS_0, &mut state_builder);
 
-    ensure!(sender.matches_account(&owner), ContractError::Unauthorized);
+        // Act
+        let result =
+            state.transfer(&TOKEN_0, 2.into(), &ADDRESS_0, &ADDRESS_1, &mut state_builder);
 
-    let params: ActionBurnParams = ctx.parameter_cursor().get()?;
+        // Assert
+        claim!(result.is_ok());
+        claim_eq!(state.balance(&TOKEN_0, &ADDRESS_0).expect_report("Token missing"), 1.into());
+        claim_eq!(state.balance(&TOKEN_0, &ADDRESS_1).expect_report("Token missing"), 2.into());
+        let holders = state.holders.get(&TOKEN_0).expect_report("Holders missing");
+        claim!(holders.contains(&ADDRESS_0));
+        claim!(holders.contains(&ADDRESS_1));
+    }
 
-    let state = host.state_mut();
+    #[concordium_test]
+    fn given_minted_and_transferred_when_record_mint_then_cap_still_applies() {
+        // Arrange
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        let token = TokenInfo {
+            max_per_account: 1.into(),
+            ..get_token_info(get_token_metadata(), 400)
+        };
+        state
+            .record_mint(ACCOUNT_0, &TOKEN_0, 1.into(), &token, &mut state_builder)
+            .expect_report("First mint failed");
+        state
+            .transfer(&TOKEN_0, 1.into(), &ADDRESS_0, &ADDRESS_1, &mut state_builder)
+            .expect_report("Transfer failed");
 
-    for token_id in params.tokens {
-        ensure!(
-            state.contains_token(&token_id),
-            ContractError::Custom(CustomContractError::TokenAlreadyCreated)
-        );
+        // Act
+        let result = state.record_mint(ACCOUNT_0, &TOKEN_0, 1.into(), &token, &mut state_builder);
 
-        state.tokens.remove(&token_id);
-        state.token_balance.remove(&token_id);
-        for (_, mut address_state) in state.state.iter_mut() {
-            address_state.balances.remove(&token_id);
-        }
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::AccountCapReached)
+        );
     }
 
-    Ok(())
-}
+    #[concordium_test]
+    fn given_collection_cap_reached_when_record_mint_then_error() {
+        // Arrange
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        let token = TokenInfo {
+            collection: Some(CollectionCap {
+                name: "dinos".to_string(),
+                max_tokens_per_account: None,
+            }),
+            ..get_token_info(get_token_metadata(), 400)
+        };
+        state.collections.insert("dinos".to_string(), Collection {
+            metadata: None,
+            max_tokens_per_account: Some(1),
+            tokens: state_builder.new_set(),
+        });
+        state
+            .record_mint(ACCOUNT_0, &TOKEN_0, 1.into(), &token, &mut state_builder)
+            .expect_report("First token failed");
+        state
+            .record_mint(ACCOUNT_0, &TOKEN_0, 1.into(), &token, &mut state_builder)
+            .expect_report("Same token should not count twice");
 
-#[receive(
-    contract = "dino_auction",
-    name = "get_owner",
-    return_value = "AccountAddress"
-)]
-fn contract_get_owner<S: HasStateApi>(
-    ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State<S>, StateApiType = S>
-) -> ContractResult<AccountAddress> {
-    Ok(ctx.owner())
-}
+        // Act
+        let result = state.record_mint(ACCOUNT_0, &TOKEN_1, 1.into(), &token, &mut state_builder);
 
-// Tests
+        // Assert
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::CollectionCapReached)
+        );
+        claim!(state
+            .record_mint(ACCOUNT_1, &TOKEN_1, 1.into(), &token, &mut state_builder)
+            .is_ok());
+    }
 
-#[concordium_cfg_test]
-mod tests {
-    use super::*;
-    use sha2::{Digest, Sha256};
-    use test_infrastructure::*;
-    #[cfg(feature = "crypto-primitives")]
-    use ed25519_dalek::{ExpandedSecretKey, PublicKey, SecretKey};
-    #[cfg(feature = "crypto-primitives")]
-    use rand::rngs::OsRng;
+    #[concordium_test]
+    fn given_collection_without_cap_when_record_mint_then_not_capped() {
+        // Arrange
+        let mut state_builder = TestStateBuilder::new();
+        let mut state = initial_state(&mut state_builder);
+        let token = TokenInfo {
+            collection: Some(CollectionCap {
+                name: "dinos".to_string(),
+                max_tokens_per_account: None,
+            }),
+            ..get_token_info(get_token_metadata(), 400)
+        };
+        state
+            .record_mint(ACCOUNT_0, &TOKEN_0, 1.into(), &token, &mut state_builder)
+            .expect_report("First token failed");
 
-    const ACCOUNT_0: AccountAddress = AccountAddress([0u8; 32]);
-    const ADDRESS_0: Address = Address::Account(ACCOUNT_0);
-    const ACCOUNT_1: AccountAddress = AccountAddress([1u8; 32]);
-    const ADDRESS_1: Address = Address::Account(ACCOUNT_1);
-    const TOKEN_0: ContractTokenId = TokenIdU32(0);
-    const TOKEN_1: ContractTokenId = TokenIdU32(42);
+        // Act
+        let result = state.record_mint(ACCOUNT_0, &TOKEN_1, 1.into(), &token, &mut state_builder);
 
-    fn get_token_metadata() -> TokenMetadata {
-        let mut hasher = Sha256::new();
-        hasher.update(b"hello world");
-        let hash = hasher.finalize();
-        let hex_hash = hex::encode(hash);
+        // Assert
+        claim!(result.is_ok());
+        claim_eq!(
+            state.collection_mints.get(&(ACCOUNT_0, "dinos".to_string())).map(|taken| *taken),
+            Some(2)
+        );
+    }
 
-        TokenMetadata {
-            url: String::from("hello"),
-            hash: hex_hash,
+    fn get_permit(entry_point: &str, payload: Vec<u8>, nonce: u64) -> PermitMessage {
+        PermitMessage {
+            contract_address: CONTRACT_0,
+            nonce,
+            timestamp: Timestamp::from_timestamp_millis(100),
+            entry_point: OwnedEntrypointName::new_unchecked(entry_point.to_string()),
+            payload,
         }
     }
 
-    fn initial_state<S: HasStateApi>(state_builder: &mut StateBuilder<S>) -> State<S> {
-        let mut state = State::empty(state_builder, PublicKeyEd25519([0u8; 32]));
+    #[concordium_test]
+    fn given_signed_burn_when_permit_then_burned_for_signer() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_self_address(CONTRACT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
 
-        let meta = get_token_metadata();
+        let payload = to_bytes(&BurnParams {
+            token_id: TOKEN_0,
+            amount: 1.into(),
+        });
+        let message = get_permit("burn", payload, 0);
 
-        state.tokens.insert(TOKEN_0, (meta.clone(), 400.into()));
-        state.tokens.insert(TOKEN_1, (meta.clone(), 1.into()));
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
 
-        state.mint(&TOKEN_0, &ADDRESS_0, state_builder);
-        state.mint(&TOKEN_1, &ADDRESS_0, state_builder);
-        state
-    }
+        // Act
+        let result =
+            execute_permit(&ctx, &mut host, &mut logger, &crypto, ACCOUNT_0, message);
 
-    #[cfg(feature = "crypto-primitives")]
-    fn create_crypto_primitives() -> (SignatureEd25519, PublicKeyEd25519) {
-        let mut csprng = OsRng {};
-        let secret_key: SecretKey = SecretKey::generate(&mut csprng);
-        let public_key: PublicKey = (&secret_key).into();
-        let expanded: ExpandedSecretKey = ExpandedSecretKey::from(&secret_key);
-        let signed = expanded.sign(&ACCOUNT_0.0, &public_key);
-        return (
-            SignatureEd25519(signed.to_bytes()),
-            PublicKeyEd25519(public_key.to_bytes()),
+        // Assert
+        claim!(result.is_ok());
+        claim_eq!(
+            host.state().balance(&TOKEN_0, &ADDRESS_0).expect_report("Token missing"),
+            0.into()
+        );
+        claim_eq!(host.state().nonces.get(&ACCOUNT_0).map(|nonce| *nonce), Some(1));
+        claim_eq!(
+            logger.logs.last(),
+            Some(&to_bytes(&ContractEvent::Nonce(NonceEvent {
+                account: ACCOUNT_0,
+                nonce: 0,
+            })))
         );
     }
 
     #[concordium_test]
-    fn test_init() {
+    fn given_wrong_nonce_when_permit_then_error() {
         // Arrange
-        let mut ctx = TestInitContext::empty();
-        let mut builder = TestStateBuilder::new();
-        let parameter = InitParams {
-            verify_key: PublicKeyEd25519([0u8; 32]),
-        };
-        let parameter_bytes = to_bytes(&parameter);
-        ctx.set_parameter(&parameter_bytes);
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_self_address(CONTRACT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+
+        let payload = to_bytes(&BurnParams {
+            token_id: TOKEN_0,
+            amount: 1.into(),
+        });
+        let message = get_permit("burn", payload, 1);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
+        let crypto = TestCryptoPrimitives::new();
 
         // Act
-        let result = contract_init(&ctx, &mut builder);
+        let result =
+            execute_permit(&ctx, &mut host, &mut logger, &crypto, ACCOUNT_0, message);
 
         // Assert
-        let state = result.expect_report("Contract initialization failed");
         claim_eq!(
-            state.tokens.iter().count(),
-            0,
-            "Only one token is initialized"
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::WrongNonce)
         );
     }
 
     #[concordium_test]
-    fn given_sender_is_owner_when_burn_auction_then_ok() {
+    fn given_unsupported_entrypoint_when_permit_then_error() {
         // Arrange
         let mut ctx = TestReceiveContext::empty();
-        ctx.set_sender(ADDRESS_0);
-        ctx.set_owner(ACCOUNT_0);
+        ctx.set_sender(ADDRESS_1);
+        ctx.set_self_address(CONTRACT_0);
+        ctx.set_metadata_slot_time(Timestamp::from_timestamp_millis(0));
+
+        let message = get_permit("setPaused", to_bytes(&true), 0);
 
+        let mut logger = TestLogger::init();
         let mut state_builder = TestStateBuilder::new();
         let state = initial_state(&mut state_builder);
         let mut host = TestHost::new(state, state_builder);
-
-        let mut tokens = collections::BTreeSet::new();
-        tokens.insert(TOKEN_0);
-        let params = ActionBurnParams { tokens };
-        let parameter_bytes = to_bytes(&params);
-        ctx.set_parameter(&parameter_bytes);
+        let crypto = TestCryptoPrimitives::new();
 
         // Act
-        let result: ContractResult<()> = contract_burn_auction(&ctx, &mut host);
+        let result =
+            execute_permit(&ctx, &mut host, &mut logger, &crypto, ACCOUNT_0, message);
 
         // Assert
-        claim!(result.is_ok());
-        claim!(host.state().tokens.get(&TOKEN_0).is_none());
-
-        let address_state = host.state().state.get(&ADDRESS_0).expect("Address missing");
-        claim!(!address_state.balances.contains(&TOKEN_0));
-        claim!(address_state.balances.contains(&TOKEN_1));
-
-        claim!(host.state().token_balance.get(&TOKEN_0).is_none());
-        claim!(host.state().token_balance.get(&TOKEN_1).is_some());
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::WrongEntrypoint)
+        );
+        claim!(!host.state().paused);
     }
 
     #[concordium_test]
-    fn given_sender_is_not_owner_when_burn_auction_then_error() {
+    fn given_entrypoints_when_supports_permit_then_only_dispatched_supported() {
         // Arrange
         let mut ctx = TestReceiveContext::empty();
-        ctx.set_sender(ADDRESS_0);
-        ctx.set_owner(ACCOUNT_1);
+
+        let parameter_bytes = to_bytes(&SupportsPermitQueryParams {
+            queries: vec![
+                OwnedEntrypointName::new_unchecked("mint".to_string()),
+                OwnedEntrypointName::new_unchecked("setPaused".to_string()),
+            ],
+        });
+        ctx.set_parameter(&parameter_bytes);
 
         let mut state_builder = TestStateBuilder::new();
         let state = initial_state(&mut state_builder);
-        let mut host = TestHost::new(state, state_builder);
+        let host = TestHost::new(state, state_builder);
 
-        let mut tokens = collections::BTreeSet::new();
-        tokens.insert(TOKEN_0);
-        let params = ActionBurnParams { tokens };
-        let parameter_bytes = to_bytes(&params);
+        // Act
+        let result = contract_supports_permit(&ctx, &host).expect_report("Query failed");
+
+        // Assert
+        claim_eq!(result.results.len(), 2);
+        claim!(matches!(result.results[0], SupportResult::Support));
+        claim!(matches!(result.results[1], SupportResult::NoSupport));
+    }
+
+    #[concordium_test]
+    fn given_cis3_when_supports_then_supported() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+
+        let parameter_bytes = to_bytes(&SupportsQueryParams {
+            queries: vec![
+                StandardIdentifierOwned::new_unchecked("CIS-2".to_string()),
+                StandardIdentifierOwned::new_unchecked("CIS-3".to_string()),
+            ],
+        });
         ctx.set_parameter(&parameter_bytes);
 
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder);
+        let host = TestHost::new(state, state_builder);
+
         // Act
-        let result: ContractResult<()> = contract_burn_auction(&ctx, &mut host);
+        let result = contract_supports(&ctx, &host).expect_report("Query failed");
 
         // Assert
-        claim!(result.is_err());
-        claim_eq!(result.unwrap_err(), ContractError::Unauthorized);
+        claim!(matches!(result.results[0], SupportResult::Support));
+        claim!(matches!(result.results[1], SupportResult::Support));
     }
 
     #[concordium_test]
-    fn given_sender_is_owner_when_init_auction_then_ok() {
+    fn given_new_token_when_init_auction_then_auction_created_event() {
         // Arrange
         let mut ctx = TestReceiveContext::empty();
         ctx.set_sender(ADDRESS_0);
-        ctx.set_owner(ACCOUNT_0);
-
-        let token_info = get_token_metadata();
 
         let mut tokens = collections::BTreeMap::new();
-        tokens.insert(TOKEN_0, (token_info.clone(), 400.into()));
-        tokens.insert(TOKEN_1, (token_info.clone(), 1.into()));
-        let parameter = AuctionInitParams { tokens };
-        let parameter_bytes = to_bytes(&parameter);
+        tokens.insert(TOKEN_0, get_token_info(get_token_metadata(), 400));
+        let parameter_bytes = to_bytes(&AuctionInitParams { tokens });
         ctx.set_parameter(&parameter_bytes);
 
+        let mut logger = TestLogger::init();
         let mut state_builder = TestStateBuilder::new();
-        let state = State::empty(&mut state_builder, PublicKeyEd25519([0u8; 32]));
+        let state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
         let mut host = TestHost::new(state, state_builder);
 
         // Act
-        let result: ContractResult<()> = contract_init_auction(&ctx, &mut host);
+        let result: ContractResult<()> = contract_init_auction(&ctx, &mut host, &mut logger);
 
         // Assert
         claim!(result.is_ok());
+        claim_eq!(
+            logger.logs,
+            [to_bytes(&ContractEvent::AuctionCreated(AuctionCreatedEvent {
+                token_id: TOKEN_0,
+                max_supply: 400.into(),
+            }))]
+        );
+    }
 
-        let max_0 = host
-            .state()
-            .tokens
-            .get(&TOKEN_0)
-            .expect_report("Token not inserted");
+    #[concordium_test]
+    fn given_cis2_event_when_wrapped_then_serialized_as_cis2() {
+        // Arrange
+        let event: ContractCis2Event = Cis2Event::Burn(BurnEvent {
+            token_id: TOKEN_0,
+            amount: 1.into(),
+            owner: ADDRESS_0,
+        });
+        let expected = to_bytes(&event);
 
-        claim_eq!(max_0.1, 400.into());
+        // Act
+        let wrapped = to_bytes(&ContractEvent::Cis2(event));
+
+        // Assert
+        claim_eq!(wrapped, expected);
     }
 
     #[concordium_test]
-    fn given_sender_is_not_owner_when_init_auction_then_error() {
+    fn given_malformed_hash_when_init_auction_then_error() {
         // Arrange
         let mut ctx = TestReceiveContext::empty();
         ctx.set_sender(ADDRESS_0);
-        ctx.set_owner(ACCOUNT_1);
-
-        let token_info = get_token_metadata();
 
+        let metadata = TokenMetadata {
+            url: "https://some.example/token/0".to_string(),
+            hash: "abc".to_string(),
+        };
         let mut tokens = collections::BTreeMap::new();
-        tokens.insert(TOKEN_0, (token_info.clone(), 400.into()));
-        let parameter = AuctionInitParams { tokens };
-        let parameter_bytes = to_bytes(&parameter);
+        tokens.insert(TOKEN_0, get_token_info(metadata, 400));
+        let parameter_bytes = to_bytes(&AuctionInitParams { tokens });
         ctx.set_parameter(&parameter_bytes);
 
+        let mut logger = TestLogger::init();
         let mut state_builder = TestStateBuilder::new();
-        let state = State::empty(&mut state_builder, PublicKeyEd25519([0u8; 32]));
+        let state = State::empty(&mut state_builder, ACCOUNT_0, PublicKeyEd25519([0u8; 32]));
         let mut host = TestHost::new(state, state_builder);
 
         // Act
-        let result: ContractResult<()> = contract_init_auction(&ctx, &mut host);
+        let result: ContractResult<()> = contract_init_auction(&ctx, &mut host, &mut logger);
 
         // Assert
-        claim!(result.is_err());
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::InvalidMetadataHash)
+        );
     }
 
     #[concordium_test]
-    #[cfg(not(feature = "crypto-primitives"))]
-    fn given_token_not_existing_when_mint_then_return_error() {
+    fn given_locked_metadata_when_update_token_metadata_then_error() {
         // Arrange
         let mut ctx = TestReceiveContext::empty();
         ctx.set_sender(ADDRESS_0);
 
-        let mut tokens = collections::BTreeSet::new();
-        tokens.insert(TOKEN_0);
-        let parameter = MintParams { 
-            tokens,
-            signature: SignatureEd25519([0u8; 64]),
+        let metadata = TokenMetadata {
+            url: "https://some.example/token/updated".to_string(),
+            hash: "00".repeat(32),
         };
-        let parameter_bytes = to_bytes(&parameter);
+        let parameter_bytes = to_bytes(&UpdateTokenMetadataParams {
+            token_id: TOKEN_0,
+            metadata: metadata.clone(),
+            lock: true,
+        });
         ctx.set_parameter(&parameter_bytes);
 
         let mut logger = TestLogger::init();
         let mut state_builder = TestStateBuilder::new();
-        let state = State::empty(&mut state_builder, PublicKeyEd25519([0u8; 32]));
+        let state = initial_state(&mut state_builder);
         let mut host = TestHost::new(state, state_builder);
-        let mut crypto = TestCryptoPrimitives::new();
-        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
+
+        contract_update_token_metadata(&ctx, &mut host, &mut logger)
+            .expect_report("First update failed");
 
         // Act
-        let result: ContractResult<()> = contract_mint(&ctx, &mut host, &mut logger, &mut crypto);
+        let result: ContractResult<()> =
+            contract_update_token_metadata(&ctx, &mut host, &mut logger);
 
         // Assert
-        claim!(result.is_err());
         claim_eq!(
-            result.unwrap_err(),
-            ContractError::Custom(CustomContractError::AuctionNotInitialized)
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::MetadataLocked)
+        );
+        let token = host.state().tokens.get(&TOKEN_0).expect_report("Token missing");
+        claim_eq!(token.metadata.url, metadata.url);
+        claim_eq!(
+            logger.logs,
+            [
+                to_bytes(&ContractEvent::Cis2(Cis2Event::TokenMetadata(TokenMetadataEvent {
+                    token_id: TOKEN_0,
+                    metadata_url: metadata.to_metadata_url(),
+                }))),
+                to_bytes(&ContractEvent::MetadataLocked(MetadataLockedEvent {
+                    token_id: TOKEN_0,
+                })),
+            ]
         );
     }
 
     #[concordium_test]
-    #[cfg(feature = "crypto-primitives")]
-    fn given_crypto_primitives_when_signature_not_correct_then_return_error() {
+    fn given_sender_without_metadata_role_when_update_token_metadata_then_error() {
         // Arrange
         let mut ctx = TestReceiveContext::empty();
-        ctx.set_sender(ADDRESS_0);
+        ctx.set_sender(ADDRESS_1);
 
-        let mut tokens = collections::BTreeSet::new();
-        tokens.insert(TOKEN_0);
+        let parameter_bytes = to_bytes(&UpdateTokenMetadataParams {
+            token_id: TOKEN_0,
+            metadata: get_token_metadata(),
+            lock: false,
+        });
+        ctx.set_parameter(&parameter_bytes);
+
+        let mut logger = TestLogger::init();
+        let mut state_builder = TestStateBuilder::new();
+        let state = initial_state(&mut state_builder);
+        let mut host = TestHost::new(state, state_builder);
 
-        let (signature, _) = create_crypto_primitives();
+        // Act
+        let result: ContractResult<()> =
+            contract_update_token_metadata(&ctx, &mut host, &mut logger);
 
-        let parameter = MintParams { 
-            tokens,
-            signature,
-        };
-        let parameter_bytes = to_bytes(&parameter);
+        // Assert
+        claim_eq!(result.expect_err("Should be error"), ContractError::Unauthorized);
+    }
+
+    #[concordium_test]
+    fn given_drops_when_airdrop_then_minted_and_hook_invoked() {
+        // Arrange
+        let mut ctx = TestReceiveContext::empty();
+        ctx.set_sender(ADDRESS_0);
+        ctx.set_self_address(CONTRACT_0);
+
+        let receiver = ContractAddress::new(2, 0);
+        let entrypoint = OwnedEntrypointName::new_unchecked("onReceivingCIS2".to_string());
+        let parameter_bytes = to_bytes(&AirdropParams {
+            drops: vec![
+                AirdropEntry {
+                    to: Receiver::from_account(ACCOUNT_1),
+                    token_id: TOKEN_0,
+                    amount: 2.into(),
+                },
+                AirdropEntry {
+                    to: Receiver::from_contract(receiver, entrypoint.clone()),
+                    token_id: TOKEN_0,
+                    amount: 1.into(),
+                },
+            ],
+        });
         ctx.set_parameter(&parameter_bytes);
 
         let mut logger = TestLogger::init();
         let mut state_builder = TestStateBuilder::new();
-        let state = State::empty(&mut state_builder, PublicKeyEd25519([0u8; 32]));
+        let state = initial_state(&mut state_builder);
         let mut host = TestHost::new(state, state_builder);
-        let mut crypto = TestCryptoPrimitives::new();
+        host.setup_mock_entrypoint(receiver, entrypoint, MockFn::returning_ok(()));
 
         // Act
-        let result: ContractResult<()> = contract_mint(&ctx, &mut host, &mut logger, &mut crypto);
+        let result: ContractResult<()> = contract_airdrop(&ctx, &mut host, &mut logger);
 
         // Assert
-        claim!(result.is_err());
+        claim!(result.is_ok());
         claim_eq!(
-            result.unwrap_err(),
-            ContractError::Unauthorized
+            host.state().balance(&TOKEN_0, &ADDRESS_1).expect_report("Token missing"),
+            2.into()
+        );
+        claim_eq!(
+            host.state()
+                .balance(&TOKEN_0, &Address::Contract(receiver))
+                .expect_report("Token missing"),
+            1.into()
         );
+        claim!(logger.logs.contains(&to_bytes(&ContractEvent::Cis2(Cis2Event::Mint(
+            MintEvent {
+                token_id: TOKEN_0,
+                amount: 2.into(),
+                owner: ADDRESS_1,
+            }
+        )))));
     }
 
-
     #[concordium_test]
-    #[cfg(feature = "crypto-primitives")]
-    fn given_crypto_primitives_when_mint_then_add_token() {
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_contract_receiver_when_mint_then_minted_to_contract() {
         // Arrange
-        let token_info = get_token_metadata();
-
         let mut ctx = TestReceiveContext::empty();
-        ctx.set_sender(ADDRESS_0);
+        set_mint_context(&mut ctx);
 
+        let receiver = ContractAddress::new(2, 0);
+        let entrypoint = OwnedEntrypointName::new_unchecked("onReceivingCIS2".to_string());
         let mut tokens = collections::BTreeSet::new();
         tokens.insert(TOKEN_0);
-
-        let (signature, verify_key) = create_crypto_primitives();
-
-        let parameter = MintParams { 
-            tokens,
-            signature
+        let parameter = MintParams {
+            tokens: get_mint_amounts(&tokens),
+            authorization: MintAuthorization::Voucher {
+                voucher: get_voucher(&tokens),
+                signature: SignatureEd25519([0u8; 64]),
+            },
+            to: Some(Receiver::from_contract(receiver, entrypoint.clone())),
         };
         let parameter_bytes = to_bytes(&parameter);
         ctx.set_parameter(&parameter_bytes);
 
         let mut logger = TestLogger::init();
         let mut state_builder = TestStateBuilder::new();
-        
-        let mut state = State::empty(&mut state_builder, verify_key);
-        state.tokens.insert(TOKEN_0, (token_info.clone(), 1.into()));
-
+        let state = initial_state(&mut state_builder);
         let mut host = TestHost::new(state, state_builder);
-        let mut crypto = TestCryptoPrimitives::new();
+        host.setup_mock_entrypoint(receiver, entrypoint, MockFn::returning_ok(()));
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
 
         // Act
-        let result: ContractResult<()> = contract_mint(&ctx, &mut host, &mut logger, &mut crypto);
+        let result: ContractResult<()> =
+            contract_mint(&ctx, &mut host, Amount::zero(), &mut logger, &crypto);
 
         // Assert
         claim!(result.is_ok());
+        claim_eq!(
+            host.state()
+                .balance(&TOKEN_0, &Address::Contract(receiver))
+                .expect_report("Token missing"),
+            1.into()
+        );
+        claim_eq!(
+            host.state().balance(&TOKEN_0, &ADDRESS_0).expect_report("Token missing"),
+            1.into()
+        );
     }
 
     #[concordium_test]
-    #[cfg(feature = "crypto-primitives")]
-    fn given_crypto_primitives_when_mint_with_empty_metadata_then_return_without_error() {
+    #[cfg(not(feature = "crypto-primitives"))]
+    fn given_failing_receiver_hook_when_mint_then_error() {
         // Arrange
         let mut ctx = TestReceiveContext::empty();
-        ctx.set_sender(ADDRESS_0);
+        set_mint_context(&mut ctx);
 
+        let receiver = ContractAddress::new(2, 0);
+        let entrypoint = OwnedEntrypointName::new_unchecked("onReceivingCIS2".to_string());
         let mut tokens = collections::BTreeSet::new();
         tokens.insert(TOKEN_0);
-
-        let (signature, verify_key) = create_crypto_primitives();
-
-        let parameter = MintParams { 
-            tokens,
-            signature
+        let parameter = MintParams {
+            tokens: get_mint_amounts(&tokens),
+            authorization: MintAuthorization::Voucher {
+                voucher: get_voucher(&tokens),
+                signature: SignatureEd25519([0u8; 64]),
+            },
+            to: Some(Receiver::from_contract(receiver, entrypoint.clone())),
         };
         let parameter_bytes = to_bytes(&parameter);
         ctx.set_parameter(&parameter_bytes);
 
         let mut logger = TestLogger::init();
         let mut state_builder = TestStateBuilder::new();
-        
-        let mut state = State::empty(&mut state_builder, verify_key);
-        state.tokens.insert(TOKEN_0, (TokenMetadata{hash:"".to_string(), url: "".to_string()}, 1.into()));
-
+        let state = initial_state(&mut state_builder);
         let mut host = TestHost::new(state, state_builder);
-        let mut crypto = TestCryptoPrimitives::new();
+        host.setup_mock_entrypoint(receiver, entrypoint, MockFn::returning_err::<()>(CallContractError::Trap));
+        let crypto = TestCryptoPrimitives::new();
+        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
 
         // Act
-        let result: ContractResult<()> = contract_mint(&ctx, &mut host, &mut logger, &mut crypto);
+        let result: ContractResult<()> =
+            contract_mint(&ctx, &mut host, Amount::zero(), &mut logger, &crypto);
 
         // Assert
-        claim!(result.is_ok());
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::InvokeContractError)
+        );
     }
 
     #[concordium_test]
-    fn when_burn_then_ok() {
+    fn given_amount_above_supply_when_airdrop_then_error() {
         // Arrange
         let mut ctx = TestReceiveContext::empty();
         ctx.set_sender(ADDRESS_0);
+        ctx.set_self_address(CONTRACT_0);
 
-        let params = BurnParams{
-            token_id: TOKEN_0
-        };
-        let parameter_bytes = to_bytes(&params);
+        let parameter_bytes = to_bytes(&AirdropParams {
+            drops: vec![AirdropEntry {
+                to: Receiver::from_account(ACCOUNT_1),
+                token_id: TOKEN_1,
+                amount: 1.into(),
+            }],
+        });
         ctx.set_parameter(&parameter_bytes);
 
         let mut logger = TestLogger::init();
@@ -1082,299 +9082,201 @@ mod tests {
         let mut host = TestHost::new(state, state_builder);
 
         // Act
-        let result: ContractResult<()> = contract_burn(&ctx, &mut host, &mut logger);
+        let result: ContractResult<()> = contract_airdrop(&ctx, &mut host, &mut logger);
 
         // Assert
-        claim!(result.is_ok());
-        
-        claim_eq!(*host.state().token_balance.get(&TOKEN_0).expect_report("Token balance not present"), 0.into());
-        claim_eq!(*host.state().token_balance.get(&TOKEN_1).expect_report("Token balance not present"), 1.into());
-
-        let balance0 = host
-            .state()
-            .balance(&TOKEN_0, &ADDRESS_0)
-            .expect_report("Token is expected to exist");
-        claim_eq!(
-            balance0,
-            0.into(),
-            "Initial tokens are owned by the contract instantiater"
-        );
-
-        let balance1 = host
-            .state()
-            .balance(&TOKEN_1, &ADDRESS_0)
-            .expect_report("Token is expected to exist");
         claim_eq!(
-            balance1,
-            1.into(),
-            "Initial tokens are owned by the contract instantiater"
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::MaxSupplyReached)
         );
     }
 
     #[concordium_test]
-    fn given_token_not_exist_when_burn_then_error() {
+    fn given_sender_is_admin_when_set_max_supply_then_updated() {
+        // Arrange
         let mut ctx = TestReceiveContext::empty();
         ctx.set_sender(ADDRESS_0);
 
-        let params = BurnParams{
-            token_id: TOKEN_0
-        };
-
-        let parameter_bytes = to_bytes(&params);
+        let parameter_bytes = to_bytes(&SetMaxSupplyParams {
+            token_id: TOKEN_1,
+            max_supply: 5.into(),
+        });
         ctx.set_parameter(&parameter_bytes);
 
         let mut logger = TestLogger::init();
         let mut state_builder = TestStateBuilder::new();
-        let state = State::empty(&mut state_builder, PublicKeyEd25519([0u8; 32]));
+        let state = initial_state(&mut state_builder);
         let mut host = TestHost::new(state, state_builder);
 
         // Act
-        let result: ContractResult<()> = contract_burn(&ctx, &mut host, &mut logger);
+        let result: ContractResult<()> = contract_set_max_supply(&ctx, &mut host, &mut logger);
 
         // Assert
-        claim_eq!(result.expect_err("Should be error"), ContractError::Custom(CustomContractError::AuctionNotInitialized));
+        claim!(result.is_ok());
+        let token = host.state().tokens.get(&TOKEN_1).expect_report("Token missing");
+        claim_eq!(token.max_supply, 5.into());
+        claim_eq!(
+            logger.logs,
+            [to_bytes(&ContractEvent::MaxSupplyUpdated(MaxSupplyUpdatedEvent {
+                token_id: TOKEN_1,
+                max_supply: 5.into(),
+            }))]
+        );
     }
 
     #[concordium_test]
-    fn given_sender_not_have_token_when_burn_then_error() {
+    fn given_max_supply_below_circulating_when_set_max_supply_then_error() {
+        // Arrange
         let mut ctx = TestReceiveContext::empty();
         ctx.set_sender(ADDRESS_0);
 
-        let params = BurnParams{
-            token_id: TOKEN_0
-        };
-
-        let parameter_bytes = to_bytes(&params);
+        let parameter_bytes = to_bytes(&SetMaxSupplyParams {
+            token_id: TOKEN_0,
+            max_supply: 0.into(),
+        });
         ctx.set_parameter(&parameter_bytes);
 
         let mut logger = TestLogger::init();
         let mut state_builder = TestStateBuilder::new();
-        let mut state = State::empty(&mut state_builder, PublicKeyEd25519([0u8; 32]));
-        state.tokens.insert(TOKEN_0, (get_token_metadata(), 1.into()));
+        let state = initial_state(&mut state_builder);
         let mut host = TestHost::new(state, state_builder);
 
         // Act
-        let result: ContractResult<()> = contract_burn(&ctx, &mut host, &mut logger);
+        let result: ContractResult<()> = contract_set_max_supply(&ctx, &mut host, &mut logger);
 
         // Assert
-        claim_eq!(result.expect_err("Should be error"), ContractError::Custom(CustomContractError::NoBalanceToBurn));
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::MaxSupplyBelowCirculating)
+        );
     }
 
     #[concordium_test]
-    #[cfg(not(feature = "crypto-primitives"))]
-    fn when_mint_then_add_token() {
+    fn given_open_bid_when_close_minting_then_closed_and_bid_returned() {
         // Arrange
-        let token_info = get_token_metadata();
-
         let mut ctx = TestReceiveContext::empty();
         ctx.set_sender(ADDRESS_0);
 
-        let mut tokens = collections::BTreeSet::new();
-        tokens.insert(TOKEN_0);
-        tokens.insert(TOKEN_1);
-        let parameter = MintParams { 
-            tokens,
-            signature: SignatureEd25519([0u8; 64]),
-        };
-        let parameter_bytes = to_bytes(&parameter);
+        let parameter_bytes = to_bytes(&AuctionParams { token_id: TOKEN_0 });
         ctx.set_parameter(&parameter_bytes);
 
         let mut logger = TestLogger::init();
         let mut state_builder = TestStateBuilder::new();
-        
-        let mut state = State::empty(&mut state_builder, PublicKeyEd25519([0u8; 32]));
-        state.tokens.insert(TOKEN_0, (token_info.clone(), 1.into()));
-        state.tokens.insert(TOKEN_1, (token_info.clone(), 1.into()));
-
+        let mut state = initial_state(&mut state_builder);
+        state
+            .place_bid(&TOKEN_0, ACCOUNT_1, Amount::from_ccd(20))
+            .expect_report("Bid failed");
         let mut host = TestHost::new(state, state_builder);
-        let mut crypto = TestCryptoPrimitives::new();
-        crypto.setup_verify_ed25519_signature_mock(|_, _, _| true);
 
         // Act
-        let result: ContractResult<()> = contract_mint(&ctx, &mut host, &mut logger, &mut crypto);
+        let result: ContractResult<()> = contract_close_minting(&ctx, &mut host, &mut logger);
 
         // Assert
         claim!(result.is_ok());
-        claim_eq!(*host.state().token_balance.get(&TOKEN_0).expect("token not present in balance"), 1.into());
-        let balance0 = host
-            .state()
-            .balance(&TOKEN_0, &ADDRESS_0)
-            .expect_report("Token is expected to exist");
+        claim!(host.state().minting_closed.contains(&TOKEN_0));
+        claim!(host.state().auctions.get(&TOKEN_0).is_none());
         claim_eq!(
-            balance0,
-            1.into(),
-            "Initial tokens are owned by the contract instantiater"
+            *host.state().pending_returns.get(&ACCOUNT_1).expect_report("No pending return"),
+            Amount::from_ccd(20)
         );
-
-        let balance1 = host
-            .state()
-            .balance(&TOKEN_1, &ADDRESS_0)
-            .expect_report("Token is expected to exist");
         claim_eq!(
-            balance1,
-            1.into(),
-            "Initial tokens are owned by the contract instantiater"
-        );
-
-        claim_eq!(logger.logs.len(), 4, "Exactly four events should be logged");
-        claim!(
-            logger.logs.contains(&to_bytes(&Cis2Event::Mint(MintEvent {
-                owner: ADDRESS_0,
+            logger.logs,
+            [to_bytes(&ContractEvent::MintingClosed(MintingClosedEvent {
                 token_id: TOKEN_0,
-                amount: ContractTokenAmount::from(1),
-            }))),
-            "Expected an event for minting TOKEN_0"
-        );
-        claim!(
-            logger.logs.contains(&to_bytes(&Cis2Event::Mint(MintEvent {
-                owner: ADDRESS_0,
-                token_id: TOKEN_1,
-                amount: ContractTokenAmount::from(1),
-            }))),
-            "Expected an event for minting TOKEN_1"
-        );
-        claim!(
-            logger.logs.contains(&to_bytes(
-                &Cis2Event::TokenMetadata::<_, ContractTokenAmount>(TokenMetadataEvent {
-                    token_id: TOKEN_0,
-                    metadata_url: token_info.to_metadata_url(),
-                })
-            )),
-            "Expected an event for token metadata for TOKEN_0"
-        );
-        claim!(
-            logger.logs.contains(&to_bytes(
-                &Cis2Event::TokenMetadata::<_, ContractTokenAmount>(TokenMetadataEvent {
-                    token_id: TOKEN_1,
-                    metadata_url: token_info.to_metadata_url(),
-                })
-            )),
-            "Expected an event for token metadata for TOKEN_1"
+            }))]
         );
     }
 
     #[concordium_test]
-    fn test_transfer_account() {
+    fn given_minting_closed_when_close_minting_then_error() {
         // Arrange
         let mut ctx = TestReceiveContext::empty();
         ctx.set_sender(ADDRESS_0);
 
-        let transfer = Transfer {
-            token_id: TOKEN_0,
-            amount: ContractTokenAmount::from(100),
-            from: ADDRESS_0,
-            to: Receiver::from_account(ACCOUNT_1),
-            data: AdditionalData::empty(),
-        };
-        let parameter = TransferParams::from(vec![transfer]);
-        let parameter_bytes = to_bytes(&parameter);
+        let parameter_bytes = to_bytes(&AuctionParams { token_id: TOKEN_0 });
         ctx.set_parameter(&parameter_bytes);
 
         let mut logger = TestLogger::init();
         let mut state_builder = TestStateBuilder::new();
-        let state = initial_state(&mut state_builder);
+        let mut state = initial_state(&mut state_builder);
+        state.minting_closed.insert(TOKEN_0);
         let mut host = TestHost::new(state, state_builder);
 
         // Act
-        let result: ContractResult<()> = contract_transfer(&ctx, &mut host, &mut logger);
+        let result: ContractResult<()> = contract_close_minting(&ctx, &mut host, &mut logger);
 
         // Assert
-        claim!(result.is_err());
-        claim_eq!(result.unwrap_err(), ContractError::Unauthorized);
+        claim_eq!(
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::MintingClosed)
+        );
+        claim!(logger.logs.is_empty());
     }
 
     #[concordium_test]
-    fn test_operator_transfer() {
+    fn given_sender_without_auction_role_when_close_minting_then_error() {
         // Arrange
         let mut ctx = TestReceiveContext::empty();
         ctx.set_sender(ADDRESS_1);
 
-        let transfer = Transfer {
-            from: ADDRESS_0,
-            to: Receiver::from_account(ACCOUNT_1),
-            token_id: TOKEN_0,
-            amount: ContractTokenAmount::from(100),
-            data: AdditionalData::empty(),
-        };
-        let parameter = TransferParams::from(vec![transfer]);
-        let parameter_bytes = to_bytes(&parameter);
+        let parameter_bytes = to_bytes(&AuctionParams { token_id: TOKEN_0 });
         ctx.set_parameter(&parameter_bytes);
 
         let mut logger = TestLogger::init();
         let mut state_builder = TestStateBuilder::new();
-        let mut state = initial_state(&mut state_builder);
-        state.add_operator(&ADDRESS_0, &ADDRESS_1, &mut state_builder);
+        let state = initial_state(&mut state_builder);
         let mut host = TestHost::new(state, state_builder);
 
         // Act
-        let result: ContractResult<()> = contract_transfer(&ctx, &mut host, &mut logger);
+        let result: ContractResult<()> = contract_close_minting(&ctx, &mut host, &mut logger);
 
         // Assert
-        claim!(result.is_err());
-        claim_eq!(result.unwrap_err(), ContractError::Unauthorized);
+        claim_eq!(result.expect_err("Should be error"), ContractError::Unauthorized);
+        claim!(!host.state().minting_closed.contains(&TOKEN_0));
     }
 
     #[concordium_test]
-    fn test_add_operator() {
+    fn given_minting_closed_when_airdrop_then_error_and_holders_kept() {
         // Arrange
         let mut ctx = TestReceiveContext::empty();
         ctx.set_sender(ADDRESS_0);
-
-        let update = UpdateOperator {
-            operator: ADDRESS_1,
-            update: OperatorUpdate::Add,
-        };
-        let parameter = UpdateOperatorParams(vec![update]);
-        let parameter_bytes = to_bytes(&parameter);
-        ctx.set_parameter(&parameter_bytes);
+        ctx.set_self_address(CONTRACT_0);
 
         let mut logger = TestLogger::init();
         let mut state_builder = TestStateBuilder::new();
         let state = initial_state(&mut state_builder);
         let mut host = TestHost::new(state, state_builder);
 
-        // Act
-        let result: ContractResult<()> = contract_update_operator(&ctx, &mut host, &mut logger);
-
-        // Assert
-        claim!(result.is_ok(), "Results in rejection");
-
-        let is_operator = host.state().is_operator(&ADDRESS_1, &ADDRESS_0);
-        claim!(is_operator, "Account should be an operator");
-
-        let operator_of_query = OperatorOfQuery {
-            address: ADDRESS_1,
-            owner: ADDRESS_0,
-        };
-
-        let operator_of_query_vector = OperatorOfQueryParams {
-            queries: vec![operator_of_query],
-        };
-        let parameter_bytes = to_bytes(&operator_of_query_vector);
+        let parameter_bytes = to_bytes(&AuctionParams { token_id: TOKEN_0 });
+        ctx.set_parameter(&parameter_bytes);
+        contract_close_minting(&ctx, &mut host, &mut logger).expect_report("Should close minting");
 
+        let parameter_bytes = to_bytes(&AirdropParams {
+            drops: vec![AirdropEntry {
+                to: Receiver::from_account(ACCOUNT_1),
+                token_id: TOKEN_0,
+                amount: 1.into(),
+            }],
+        });
         ctx.set_parameter(&parameter_bytes);
 
-        let result: ContractResult<OperatorOfQueryResponse> = contract_operator_of(&ctx, &host);
+        // Act
+        let result: ContractResult<()> = contract_airdrop(&ctx, &mut host, &mut logger);
 
+        // Assert
         claim_eq!(
-            result.expect_report("Failed getting result value").0,
-            [true],
-            "Account should be an operator in the query response"
+            result.expect_err("Should be error"),
+            ContractError::Custom(CustomContractError::MintingClosed)
         );
-
-        claim_eq!(logger.logs.len(), 1, "One event should be logged");
         claim_eq!(
-            logger.logs[0],
-            to_bytes(
-                &Cis2Event::<ContractTokenId, ContractTokenAmount>::UpdateOperator(
-                    UpdateOperatorEvent {
-                        owner: ADDRESS_0,
-                        operator: ADDRESS_1,
-                        update: OperatorUpdate::Add,
-                    }
-                )
-            ),
-            "Incorrect event emitted"
-        )
+            host.state().balance(&TOKEN_0, &ADDRESS_0).expect_report("Balance missing"),
+            1.into()
+        );
+        claim_eq!(
+            logger.logs,
+            [to_bytes(&ContractEvent::MintingClosed(MintingClosedEvent {
+                token_id: TOKEN_0,
+            }))]
+        );
     }
 }
@@ -4,8 +4,8 @@ import { useParams } from "react-router";
 import { invokeContract, jsonStringify, updateContract } from "../modules/contractClients";
 import { Box, Button, CircularProgress, Container, Grid, Paper, Typography } from "@mui/material";
 import { Login, Logout } from "@mui/icons-material";
-import { IdStatementBuilder } from "@concordium/web-sdk";
-import { getChallenge, getSignature } from "../modules/verifierClient";
+import { getChallenge, getSignature, getStatement } from "../modules/verifierClient";
+import { DEFAULT_STATEMENT_ID } from "../utils/config";
 
 interface AuctionInfo {
     provider: WalletApi | undefined,
@@ -51,20 +51,26 @@ export function Auction(info: AuctionInfo) {
 
             const challenge = await getChallenge(account!);
 
-            const statementBuilder = new IdStatementBuilder();
-            statementBuilder.addEUNationality();
-            const statement = statementBuilder.getStatement();
+            const required = await invokeContract<{ Some?: [string] }>(
+                provider!, "viewStatementId", contractId!, { token_id: auctionId }, setError);
+            const statementId = required?.Some?.[0] ?? DEFAULT_STATEMENT_ID;
+            const statement = await getStatement(statementId);
 
-            const proof = await provider!.requestIdProof(account!, statement!, challenge)
+            const proof = await provider!.requestIdProof(account!, statement, challenge)
 
-            const signature = await getSignature(challenge, proof);
+            const { voucher, signature } = await getSignature(
+                challenge, proof, statementId, contractId!, [auctionId!]);
 
             const param = {
-                tokens: [auctionId],
-                signature
+                tokens: [[auctionId, "1"]],
+                authorization: { Voucher: { voucher, signature } },
+                to: { None: [] }
             }
 
-            updateContract(provider!, contractId!, 0n, account!, "mint", param)
+            const price = await invokeContract<string>(
+                provider!, "currentPrice", contractId!, { token_id: auctionId }, setError);
+
+            updateContract(provider!, contractId!, BigInt(price ?? "0"), account!, "mint", param)
                 .then(() => {
                     getIsIn();
                 })
@@ -82,6 +88,7 @@ export function Auction(info: AuctionInfo) {
 
             const param = {
                 token_id: auctionId,
+                amount: "1",
             }
 
             updateContract(provider!, contractId!, 0n, account!, "burn", param)
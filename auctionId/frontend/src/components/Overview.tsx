@@ -1,7 +1,7 @@
 import { WalletApi } from "@concordium/browser-wallet-api-helpers";
 import { Box, Button, CircularProgress, Container, Divider, Grid, IconButton, List, ListItem, ListItemIcon, ListItemText, Paper, TextField, Typography } from "@mui/material";
 import { useCallback, useEffect } from "react";
-import { CONTRACT_TOKEN_BYTE_SIZE, DEFAULT_VERIFY_KEY } from "../utils/config";
+import { CONTRACT_TOKEN_BYTE_SIZE, DEFAULT_VERIFY_KEY, VIEW_PAGE_LIMIT, BURN_HOLDERS_LIMIT, DEFAULT_STATEMENT_ID } from "../utils/config";
 import { useState } from "react";
 import { Buffer } from "buffer/";
 import { ContractInitializedEvent, initContract, invokeContract, jsonStringify, updateContract } from "../modules/contractClients";
@@ -16,8 +16,15 @@ interface OverviewInfo {
     setContractId: (contractId: bigint) => void
 }
 
+interface ViewToken {
+    token_id: string,
+    max_supply: string,
+    circulating_supply: string,
+    metadata_locked: boolean
+}
+
 interface View {
-    tokens: string[]
+    tokens: ViewToken[]
 }
 
 const tokenValidate = (
@@ -62,7 +69,7 @@ export function Overview(info: OverviewInfo) {
         if (!provider || !contractId) {
             return;
         }
-        invokeContract<View>(provider, "view", contractId, {}, setError)
+        invokeContract<View>(provider, "viewTokens", contractId, { cursor: 0, limit: VIEW_PAGE_LIMIT }, setError)
             .then((view: View | undefined) => setView(view));
     }, [contractId, provider, setError]);
 
@@ -72,7 +79,7 @@ export function Overview(info: OverviewInfo) {
         }
         getView();
 
-        invokeContract<string>(provider, "get_owner", contractId, {}, setError)
+        invokeContract<string>(provider, "get_admin", contractId, {}, setError)
             .then(setOwner)
     }, [contractId, getView, provider, setError])
 
@@ -83,7 +90,8 @@ export function Overview(info: OverviewInfo) {
     const submitBurnAuction = useCallback((event: React.FormEvent<HTMLFormElement>) => {
         event.preventDefault();
         const input = {
-            tokens: [tokenToBurn!]
+            tokens: [tokenToBurn!],
+            limit: BURN_HOLDERS_LIMIT
         };
         setProcessing(true);
         updateContract(provider!, contractId!, 0n, account!, "burn_auction", input)
@@ -103,7 +111,20 @@ export function Overview(info: OverviewInfo) {
     const initAuction = useCallback(async (event: React.FormEvent<HTMLFormElement>) => {
         event.preventDefault();
         const tokens = {
-            [tokenToInit!]: [{ url: tokenToInit, hash: '' }, tokenToInitQuantity.toString()]
+            [tokenToInit!]: {
+                metadata: { url: tokenToInit, hash: '' },
+                max_supply: tokenToInitQuantity.toString(),
+                max_per_account: "1",
+                transfer_policy: { Soulbound: [] },
+                start: { None: [] },
+                end: { None: [] },
+                collection: { None: [] },
+                merkle_root: { None: [] },
+                sealed_bid: { None: [] },
+                price: { None: [] },
+                payouts: [],
+                statement_id: { Some: [DEFAULT_STATEMENT_ID] }
+            }
         }
         const input = {
             tokens: Object.keys(tokens).map((tokenId) => [tokenId, tokens[tokenId]])
@@ -182,7 +203,7 @@ export function Overview(info: OverviewInfo) {
                             </Grid>
                             <Grid item xs={12}>
                                 <List>
-                                    {view.tokens.map((token: string, idx: number) =>
+                                    {view.tokens.map(({ token_id: token }: ViewToken, idx: number) =>
                                         <Box key={token}>
                                             {idx !== 0 &&
                                                 <Divider component="li" />
@@ -7,19 +7,44 @@ export async function getChallenge(account: string): Promise<string> {
     return body.challenge;
 }
 
-export async function getStatement(): Promise<IdStatement> {
-    const response = await fetch(`${VERIFIER_URL}/statement`,
+export async function getStatement(statementId: string): Promise<IdStatement> {
+    const response = await fetch(`${VERIFIER_URL}/statement/${statementId}`,
     { method: "get" });
   const body = await response.json();
-  return JSON.parse(body);
+  return body;
+}
+
+export interface MintVoucher {
+    contract: { index: number, subindex: number },
+    account: string,
+    statement_id: string,
+    tokens: string[],
+    nonce: number,
+    expiry: string
+}
+
+export interface SignedMintVoucher {
+    voucher: MintVoucher,
+    signature: string
 }
 
-export async function getSignature(challenge: string, proof: IdProofOutput): Promise<string> {
+export async function getSignature(
+    challenge: string,
+    proof: IdProofOutput,
+    statementId: string,
+    contractId: bigint,
+    tokens: string[]): Promise<SignedMintVoucher> {
     const response = await fetch(`${VERIFIER_URL}/prove`,
     {
       method: "post",
       headers: new Headers({ 'content-type': 'application/json' }),
-      body: JSON.stringify({ challenge, proof })
+      body: JSON.stringify({
+        challenge,
+        proof,
+        statement_id: statementId,
+        contract: { index: Number(contractId), subindex: 0 },
+        tokens
+      })
     });
   const body = await response.json();
   return body;
@@ -6,10 +6,13 @@ import { toBuffer } from "@concordium/web-sdk";
 
 export const DEFAULT_VERIFY_KEY: string = Key.verify_key;
 export const VERIFIER_URL: string = "http://localhost:8020/api";
+export const DEFAULT_STATEMENT_ID: string = "eu-nationality";
 
 export const MODULE_REF: string = "6054d082a164b637d739d0ef110aa43a3cb9dd2d612ca24a8c01f5c313daf318";
 export const CONTRACT_NAME: string = "dino_auction";
 export const CONTRACT_TOKEN_BYTE_SIZE: number = 4;
+export const VIEW_PAGE_LIMIT: number = 100;
+export const BURN_HOLDERS_LIMIT: number = 100;
 
 export const MAX_CONTRACT_EXECUTION_ENERGY: bigint = BigInt(30_000);
 
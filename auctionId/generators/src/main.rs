@@ -1,13 +1,19 @@
 use ed25519_dalek::{SecretKey, PublicKey};
 use rand::rngs::OsRng;
 use serde::Serialize;
-use std::{env, fs::File, io::Write};
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeMap, env, fs, fs::File, io::Write};
 
 const DEFAULT_PATH_CONTRACT: &str = "./contract/input/init.json";
 const DEFAULT_PATH_FRONTEND: &str = "./frontend/src/keys.json";
 const DEFAULT_PATH_VERIFIER: &str = "./verifier/keys.json";
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("allowlist") {
+        return generate_allowlist(&args[1..]);
+    }
+
     let mut csprng = OsRng {};
     let secret_key: SecretKey = SecretKey::generate(&mut csprng);
     let public_key: PublicKey = (&secret_key).into();
@@ -36,6 +42,87 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Build the Merkle root and a proof per address from a CSV with an account
+/// address in the first column.
+fn generate_allowlist(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    if args.len() != 2 {
+        return Err("Usage: allowlist <csv> <output>".into());
+    }
+
+    let csv = fs::read_to_string(&args[0])?;
+    let mut leaves = BTreeMap::new();
+    for line in csv.lines() {
+        let address = line.split(',').next().unwrap_or_default().trim();
+        if address.is_empty() || address == "address" {
+            continue;
+        }
+        let leaf = hash(&decode_address(address)?);
+        leaves.insert(leaf, address.to_string());
+    }
+
+    if leaves.is_empty() {
+        return Err("No addresses in allowlist".into());
+    }
+
+    let mut levels = vec![leaves.keys().copied().collect::<Vec<_>>()];
+    while levels.last().unwrap().len() > 1 {
+        let next = levels
+            .last()
+            .unwrap()
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => hash_pair(left, right),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+        levels.push(next);
+    }
+
+    let root = levels.last().unwrap()[0];
+    let proofs = leaves
+        .iter()
+        .enumerate()
+        .map(|(index, (_, address))| (address.clone(), get_proof(&levels, index)))
+        .collect();
+
+    println!("Merkle root: {}", hex::encode(root));
+
+    store_to_file(Allowlist { root: hex::encode(root), proofs }, args[1].to_owned())?;
+
+    println!("Successfully created allowlist with {} addresses", leaves.len());
+
+    Ok(())
+}
+
+fn get_proof(levels: &[Vec<[u8; 32]>], mut index: usize) -> Vec<String> {
+    let mut proof = Vec::new();
+    for level in &levels[..levels.len() - 1] {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(hex::encode(sibling));
+        }
+        index /= 2;
+    }
+    proof
+}
+
+fn decode_address(address: &str) -> Result<[u8; 32], Box<dyn std::error::Error>> {
+    let bytes = bs58::decode(address).with_check(Some(1)).into_vec()?;
+    let bytes: [u8; 32] = bytes[1..]
+        .try_into()
+        .map_err(|_| format!("Invalid account address {address}"))?;
+    Ok(bytes)
+}
+
+fn hash(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hash(&[left.as_slice(), right.as_slice()].concat())
+}
+
 fn store_to_file<T: Serialize>(object: T, path: String) -> Result<(), Box<dyn std::error::Error>> {
     let serialized_input = serde_json::to_string(&object)?;
 
@@ -64,6 +151,12 @@ fn get_output_path() -> (String, String, String) {
 
 }
 
+#[derive(Serialize)]
+struct Allowlist {
+    root: String,
+    proofs: BTreeMap<String, Vec<String>>,
+}
+
 #[derive(Serialize)]
 struct Frontend {
     verify_key: String,
@@ -6,16 +6,23 @@ use concordium_rust_sdk::{
         id_proof_types::Statement,
         types::{AccountAddress, AccountCredentialWithoutProofs},
     },
+    types::smart_contracts::concordium_contracts_common::{self as contracts_common, Timestamp},
     v2::BlockIdentifier,
 };
 use log::warn;
 use rand::Rng;
+use std::collections::HashMap;
 use std::convert::Infallible;
+use std::sync::Arc;
 use std::time::SystemTime;
 use warp::{http::StatusCode, Rejection};
 
 static CHALLENGE_EXPIRY_SECONDS: u64 = 600;
 static CLEAN_INTERVAL_SECONDS: u64 = 600;
+static VOUCHER_EXPIRY_SECONDS: u64 = 600;
+/// Nonces are drawn below 2^53 so they survive the round trip through a
+/// JavaScript number in the frontend.
+static MAX_NONCE: u64 = 1 << 53;
 
 pub async fn handle_get_challenge(
     state: Server,
@@ -61,14 +68,27 @@ async fn get_challenge_worker(
     Ok(ChallengeResponse { challenge })
 }
 
+pub async fn handle_get_statement(
+    statements: Arc<HashMap<String, Statement<ArCurve, AttributeKind>>>,
+    statement_id: String,
+) -> Result<impl warp::Reply, Rejection> {
+    match statements.get(&statement_id) {
+        Some(statement) => Ok(warp::reply::json(statement)),
+        None => {
+            warn!("Unknown statement {}.", statement_id);
+            Err(warp::reject::custom(InjectStatementError::UnknownStatement))
+        }
+    }
+}
+
 pub async fn handle_provide_proof(
     client: concordium_rust_sdk::v2::Client,
     state: Server,
-    statement: Statement<ArCurve, AttributeKind>,
+    statements: Arc<HashMap<String, Statement<ArCurve, AttributeKind>>>,
     request: ChallengedProof,
     key_pair: KeyPair,
 ) -> Result<impl warp::Reply, Rejection> {
-    match check_proof_worker(client, state, request, statement, key_pair).await {
+    match check_proof_worker(client, state, request, statements, key_pair).await {
         Ok(r) => Ok(warp::reply::json(&r)),
         Err(e) => {
             warn!("Request is invalid {:#?}.", e);
@@ -81,9 +101,13 @@ async fn check_proof_worker(
     mut client: concordium_rust_sdk::v2::Client,
     state: Server,
     request: ChallengedProof,
-    statement: Statement<ArCurve, AttributeKind>,
+    statements: Arc<HashMap<String, Statement<ArCurve, AttributeKind>>>,
     key_pair: KeyPair,
-) -> Result<String, InjectStatementError> {
+) -> Result<MintVoucherResponse, InjectStatementError> {
+    let statement = statements
+        .get(&request.statement_id)
+        .ok_or(InjectStatementError::UnknownStatement)?;
+
     let status = {
         let challenges = state
             .challenges
@@ -144,10 +168,28 @@ async fn check_proof_worker(
         .map_err(|_| InjectStatementError::LockingError)?;
 
     challenges.remove(&request.challenge);
-    
-    let sig = key_pair.sign(&acc_info.response.account_address.0);
 
-    Ok(hex::encode_upper(sig.sig))
+    let expiry = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_err(|_| InjectStatementError::Clock)?
+        .as_millis() as u64
+        + VOUCHER_EXPIRY_SECONDS * 1000;
+
+    let voucher = MintVoucher {
+        contract: request.contract,
+        account: contracts_common::AccountAddress(acc_info.response.account_address.0),
+        statement_id: request.statement_id,
+        tokens: request.tokens,
+        nonce: rand::thread_rng().gen_range(0..MAX_NONCE),
+        expiry: Timestamp::from_timestamp_millis(expiry),
+    };
+
+    let sig = key_pair.sign(&contracts_common::to_bytes(&voucher));
+
+    Ok(MintVoucherResponse {
+        voucher,
+        signature: hex::encode_upper(sig.sig),
+    })
 }
 
 pub async fn handle_clean_state(state: Server) -> anyhow::Result<()> {
@@ -194,6 +236,10 @@ pub async fn handle_rejection(err: Rejection) -> Result<impl warp::Reply, Infall
         let code = StatusCode::NOT_FOUND;
         let message = "Session not found.";
         Ok(make_reply(message.into(), code))
+    } else if let Some(InjectStatementError::UnknownStatement) = err.find() {
+        let code = StatusCode::NOT_FOUND;
+        let message = "Statement not found.";
+        Ok(make_reply(message.into(), code))
     } else if err
         .find::<warp::filters::body::BodyDeserializeError>()
         .is_some()
@@ -37,11 +37,11 @@ struct IdVerifierConfig {
     log_level: log::LevelFilter,
 
     #[clap(
-        long = "statement",
-        help = "The statement that the server accepts proofs for.",
-        default_value = r#"[{"type":"AttributeInSet","attributeTag":"nationality","set":["AT","BE","BG","CY","CZ","DK","EE","FI","FR","DE","GR","HU","IE","IT","LV","LT","LU","MT","NL","PL","PT","RO","SK","SI","ES","SE","HR"]}]"#,
+        long = "statements",
+        help = "The statements that the server accepts proofs for, by the statement id tokens require.",
+        default_value = r#"{"eu-nationality":[{"type":"AttributeInSet","attributeTag":"nationality","set":["AT","BE","BG","CY","CZ","DK","EE","FI","FR","DE","GR","HU","IE","IT","LV","LT","LU","MT","NL","PL","PT","RO","SK","SI","ES","SE","HR"]}]}"#,
     )]
-    statement: String,
+    statements: String,
 
     #[structopt(
         help = "path to keys",
@@ -75,7 +75,10 @@ async fn main() -> anyhow::Result<()> {
     let prove_state = state.clone();
     let challenge_state = state.clone();
 
-    let statement: Statement<ArCurve, AttributeKind> = serde_json::from_str(&app.statement)?;
+    let statements: HashMap<String, Statement<ArCurve, AttributeKind>> =
+        serde_json::from_str(&app.statements)?;
+    let statements = Arc::new(statements);
+    let statement_state = statements.clone();
 
     let cors = warp::cors()
         .allow_any_origin()
@@ -90,8 +93,10 @@ async fn main() -> anyhow::Result<()> {
         });
 
     let get_statement = warp::get()
-        .and(warp::path!("api" / "statement"))
-        .map(move || warp::reply::json(&app.statement));        
+        .and(warp::path!("api" / "statement" / String))
+        .and_then(move |statement_id: String| {
+            handle_get_statement(statement_state.clone(), statement_id)
+        });
 
     let provide_proof = warp::post()
         .and(warp::filters::body::content_length_limit(50 * 1024))
@@ -111,7 +116,7 @@ async fn main() -> anyhow::Result<()> {
             handle_provide_proof(
                 client.clone(),
                 prove_state.clone(),
-                statement.clone(),
+                statements.clone(),
                 request,
                 kp,
             )
@@ -5,11 +5,17 @@ use concordium_rust_sdk::{
         constants::{ArCurve, AttributeKind},
         id_proof_types::Proof,
         types::{AccountAddress, GlobalContext},
-    }, types::CredentialRegistrationID,
+    },
+    types::{
+        smart_contracts::concordium_contracts_common::{
+            self as contracts_common, ContractAddress, Serial, Timestamp, Write,
+        },
+        CredentialRegistrationID,
+    },
 };
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap},
     sync::{Arc, Mutex},
     time::SystemTime,
 };
@@ -20,6 +26,8 @@ pub enum InjectStatementError {
     LockingError,
     #[error("Unknown session")]
     UnknownSession,
+    #[error("Unknown statement")]
+    UnknownStatement,
     #[error("Issues with credentials")]
     Credential,
     #[error("Not allowed")]
@@ -30,6 +38,8 @@ pub enum InjectStatementError {
     NodeAccess(#[from] QueryError),
     #[error("Error parsing challenge")]
     ChallengeParse,
+    #[error("System clock is before unix epoch")]
+    Clock,
 }
 
 impl warp::reject::Reject for InjectStatementError {}
@@ -59,7 +69,10 @@ pub struct ChallengeResponse {
 #[derive(Deserialize, Serialize, Clone)]
 pub struct ChallengedProof {
     pub challenge: String,
-    pub proof: ProofWithContext
+    pub proof: ProofWithContext,
+    pub statement_id: String,
+    pub contract: ContractAddress,
+    pub tokens: BTreeSet<TokenIdU32>,
 }
 
 #[derive(Deserialize, Serialize, Clone)]
@@ -68,6 +81,64 @@ pub struct ProofWithContext {
     pub proof: Versioned<Proof<ArCurve, AttributeKind>>
 }
 
+/// Token id as used by the contract, given in JSON as the hex encoding of its
+/// little endian bytes.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(try_from = "String", into = "String")]
+pub struct TokenIdU32(pub u32);
+
+impl TryFrom<String> for TokenIdU32 {
+    type Error = hex::FromHexError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let mut bytes = [0u8; 4];
+        hex::decode_to_slice(value, &mut bytes)?;
+        Ok(TokenIdU32(u32::from_le_bytes(bytes)))
+    }
+}
+
+impl From<TokenIdU32> for String {
+    fn from(token_id: TokenIdU32) -> Self {
+        hex::encode(token_id.0.to_le_bytes())
+    }
+}
+
+impl Serial for TokenIdU32 {
+    fn serial<W: Write>(&self, out: &mut W) -> Result<(), W::Err> {
+        out.write_u8(4)?;
+        self.0.serial(out)
+    }
+}
+
+/// Mint voucher signed by the verifier. Serializes exactly like `MintVoucher`
+/// in the contract, which checks the signature against these bytes.
+#[derive(Serialize, Clone)]
+pub struct MintVoucher {
+    pub contract: ContractAddress,
+    pub account: contracts_common::AccountAddress,
+    pub statement_id: String,
+    pub tokens: BTreeSet<TokenIdU32>,
+    pub nonce: u64,
+    pub expiry: Timestamp,
+}
+
+impl Serial for MintVoucher {
+    fn serial<W: Write>(&self, out: &mut W) -> Result<(), W::Err> {
+        self.contract.serial(out)?;
+        self.account.serial(out)?;
+        self.statement_id.serial(out)?;
+        self.tokens.serial(out)?;
+        self.nonce.serial(out)?;
+        self.expiry.serial(out)
+    }
+}
+
+#[derive(Serialize)]
+pub struct MintVoucherResponse {
+    pub voucher: MintVoucher,
+    pub signature: String,
+}
+
 #[derive(Serialize)]
 pub struct ErrorResponse {
     pub code: u16,